@@ -27,40 +27,40 @@ fn function(input: input::ResponseData) -> Result<output::FunctionResult> {
     let cart_lines = input.cart.lines;
 
     if cart_lines.is_empty() || config.percentage == 0.0 {
-        return Ok(output::FunctionResult {
-            discount_application_strategy: output::DiscountApplicationStrategy::FIRST,
-            discounts: vec![],
-        });
+        function_bail!(
+            output::FunctionResult {
+                discount_application_strategy: output::DiscountApplicationStrategy::FIRST,
+                discounts: vec![],
+            },
+            "No cart lines or discount configured"
+        );
     }
 
     let mut targets = vec![];
     for line in cart_lines {
         if line.quantity >= config.quantity {
-            targets.push(output::Target::ProductVariant(
-                output::ProductVariantTarget {
-                    id: match line.merchandise {
-                        input::InputCartLinesMerchandise::ProductVariant(variant) => variant.id,
-                        _ => continue,
-                    },
-                    quantity: None,
-                },
-            ));
+            let input::InputCartLinesMerchandise::ProductVariant(variant) = line.merchandise else {
+                continue;
+            };
+            targets.push(output::Target::product_variant(variant.id, None));
         }
     }
 
     if targets.is_empty() {
-        return Ok(output::FunctionResult {
-            discount_application_strategy: output::DiscountApplicationStrategy::FIRST,
-            discounts: vec![],
-        });
+        function_bail!(
+            output::FunctionResult {
+                discount_application_strategy: output::DiscountApplicationStrategy::FIRST,
+                discounts: vec![],
+            },
+            "No cart line has a quantity of at least {}",
+            config.quantity
+        );
     }
     Ok(output::FunctionResult {
         discounts: vec![output::Discount {
             message: None,
             targets,
-            value: output::Value::Percentage(output::Percentage {
-                value: Decimal(config.percentage),
-            }),
+            value: output::Value::percentage(Decimal(config.percentage)),
         }],
         discount_application_strategy: output::DiscountApplicationStrategy::FIRST,
     })
@@ -1,112 +1,58 @@
 use super::*;
-use shopify_function::{run_function_with_input, Result};
+
+// Generates one test per directory in `fixtures`, comparing the function
+// output for `input.json` against `expected.json`.
+shopify_function_test_fixtures!(function = function, path = "fixtures");
 
 #[test]
-fn test_discount_with_no_configuration() -> Result<()> {
-    let result = run_function_with_input(
-        function,
-        r#"
-            {
-                "cart": {
-                    "lines": [
-                        {
-                            "cost": {
-                                "totalAmount": {
-                                    "amount": "0"
-                                }
-                            },
-                            "merchandise": {
-                                "__typename": "ProductVariant",
-                                "id": "gid://shopify/ProductVariant/0"
-                            },
-                            "quantity": 5
-                        },
-                        {
-                            "cost": {
-                                "totalAmount": {
-                                    "amount": "0"
-                                }
-                            },
-                            "merchandise": {
-                                "__typename": "ProductVariant",
-                                "id": "gid://shopify/ProductVariant/1"
-                            },
-                            "quantity": 1
-                        }
-                    ]
-                },
-                "discountNode": {
-                    "metafield": null
-                }
-            }
-        "#,
-    )?;
-    let expected = crate::output::FunctionResult {
-        discounts: vec![],
-        discount_application_strategy: crate::output::DiscountApplicationStrategy::FIRST,
-    };
-    assert_eq!(result, expected);
+fn test_cart_line_total_amount() -> Result<()> {
+    let input: input::ResponseData = serde_json::from_str(include_str!(
+        "../fixtures/discount_with_configuration/input.json"
+    ))?;
+
+    let totals: Vec<Decimal> = input
+        .cart
+        .lines
+        .iter()
+        .map(CostTotalAmount::total_amount_decimal)
+        .collect();
+
+    assert_eq!(totals, vec![Decimal(0.0), Decimal(10.0)]);
     Ok(())
 }
 
 #[test]
-fn test_discount_with_configuration() -> Result<()> {
-    let result = run_function_with_input(
-        function,
-        r#"
-            {
-                "cart": {
-                    "lines": [
-                        {
-                            "cost": {
-                                "totalAmount": {
-                                    "amount": "0"
-                                }
-                            },
-                            "merchandise": {
-                                "__typename": "ProductVariant",
-                                "id": "gid://shopify/ProductVariant/0"
-                            },
-                            "quantity": 5
-                        },
-                        {
-                            "cost": {
-                                "totalAmount": {
-                                    "amount": "10"
-                                }
-                            },
-                            "merchandise": {
-                                "__typename": "ProductVariant",
-                                "id": "gid://shopify/ProductVariant/1"
-                            },
-                            "quantity": 1
-                        }
-                    ]
-                },
-                "discountNode": {
-                    "metafield": {
-                        "value": "{\"quantity\": 5, \"percentage\": 10}"
-                    }
-                }
-            }
-        "#,
-    )?;
-    let expected = crate::output::FunctionResult {
-        discounts: vec![crate::output::Discount {
-            message: None,
-            targets: vec![crate::output::Target::ProductVariant(
-                output::ProductVariantTarget {
-                    id: "gid://shopify/ProductVariant/0".to_string(),
-                    quantity: None,
-                },
-            )],
-            value: crate::output::Value::Percentage(output::Percentage {
-                value: Decimal(10.0),
-            }),
-        }],
-        discount_application_strategy: crate::output::DiscountApplicationStrategy::FIRST,
-    };
+fn test_merchandise_kind() -> Result<()> {
+    let input: input::ResponseData = serde_json::from_str(include_str!(
+        "../fixtures/discount_with_configuration/input.json"
+    ))?;
+
+    let kinds: Vec<&str> = input
+        .cart
+        .lines
+        .iter()
+        .map(|line| line.merchandise.kind())
+        .collect();
 
-    assert_eq!(result, expected);
+    assert_eq!(kinds, vec!["ProductVariant", "ProductVariant"]);
     Ok(())
 }
+
+#[test]
+fn test_target_try_into_variant() {
+    let target =
+        output::Target::product_variant("gid://shopify/ProductVariant/1".to_string(), None);
+
+    let variant: &output::ProductVariantTarget = (&target).try_into().unwrap();
+    assert_eq!(variant.id, "gid://shopify/ProductVariant/1");
+
+    let variant: output::ProductVariantTarget = target.try_into().unwrap();
+    assert_eq!(variant.quantity, None);
+}
+
+#[test]
+fn test_value_try_into_other_variant() {
+    let value = output::Value::percentage(Decimal(10.0));
+
+    assert_eq!(output::FixedAmount::try_from(value.clone()), Err(value));
+}
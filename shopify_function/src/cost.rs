@@ -0,0 +1,12 @@
+use crate::scalars::Decimal;
+
+/// Implemented by generated input types selecting `cost { totalAmount { amount } }`,
+/// such as cart lines, to skip the nested field access.
+///
+/// ```ignore
+/// let total: Decimal = cart_line.total_amount_decimal();
+/// ```
+pub trait CostTotalAmount {
+    /// Returns `cost.totalAmount.amount`.
+    fn total_amount_decimal(&self) -> Decimal;
+}
@@ -1,3 +1,16 @@
 pub type CountryCode = String;
 pub type CurrencyCode = String;
 pub type LanguageCode = String;
+
+/// The error returned when parsing a generated enum from a string that isn't
+/// one of the enum's values in the schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownEnumValue(pub String);
+
+impl std::fmt::Display for UnknownEnumValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown enum value: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownEnumValue {}
@@ -15,19 +15,53 @@
 //!     /* ... */
 //! }
 //! ```
+//!
+//! With `visibility = "pub(crate)"`, none of the generated types are part of
+//! the crate's public API, so re-exporting one fails to compile:
+//!
+//! ```compile_fail,E0365
+//! mod types {
+//!     use shopify_function::prelude::*;
+//!
+//!     generate_types!(
+//!         query_path = "./tests/fixtures/input.graphql",
+//!         schema_path = "./tests/fixtures/schema.graphql",
+//!         visibility = "pub(crate)"
+//!     );
+//! }
+//!
+//! pub use types::input::ResponseData;
+//! ```
 
-pub use shopify_function_macro::{generate_types, shopify_function, shopify_function_target};
+pub use shopify_function_macro::{
+    generate_types, shopify_function, shopify_function_target, shopify_function_test_fixtures,
+};
 
+mod cost;
 #[doc(hidden)]
 pub mod enums;
 /// Only used for struct generation.
 #[doc(hidden)]
 pub mod scalars;
+pub mod with;
+
+pub use cost::CostTotalAmount;
+pub use enums::UnknownEnumValue;
+/// Re-exported so config types can store small lists inline with the same
+/// `SmallVec` version this crate enables serde support for.
+#[cfg(feature = "smallvec")]
+pub use smallvec;
 
 pub mod prelude {
+    pub use crate::cost::CostTotalAmount;
     pub use crate::enums::*;
     pub use crate::scalars::*;
-    pub use shopify_function_macro::{generate_types, shopify_function, shopify_function_target};
+    pub use crate::{
+        assert_decimal_eq, assert_input_type, assert_output_within, function_bail, map_enum,
+    };
+    pub use shopify_function_macro::{
+        generate_types, shopify_function, shopify_function_target, shopify_function_test_fixtures,
+    };
 }
 
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
@@ -46,5 +80,212 @@ where
     f(parsed_payload)
 }
 
+/// Serializes the function output into the exact bytes the Function writes
+/// to its output stream. This function is provided as a helper when writing
+/// tests that assert on the serialized output.
+pub fn output_bytes<O: serde::Serialize>(output: &O) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec(output)?)
+}
+
+/// Logs a message to STDERR, which is captured in the Function run logs, and
+/// returns early with `Ok` of the given result.
+///
+/// ```ignore
+/// if cart_lines.is_empty() {
+///     function_bail!(output::FunctionResult { discounts: vec![] }, "Cart is empty");
+/// }
+/// ```
+#[macro_export]
+macro_rules! function_bail {
+    ($result:expr, $($message:tt)+) => {{
+        eprintln!($($message)+);
+        return ::std::result::Result::Ok($result);
+    }};
+}
+
+/// Implements `From<Source> for Target` between two enums, mapping each
+/// variant explicitly. Since the generated `match` is exhaustive, adding a
+/// variant to the source enum fails to compile until it is mapped.
+///
+/// ```ignore
+/// map_enum!(input::CountryCode => output::CountryCode {
+///     AC => AC,
+///     CA => CA,
+///     Other(code) => Other(code),
+/// });
+/// ```
+///
+/// Leaving a source variant unmapped is a compile error:
+///
+/// ```compile_fail,E0004
+/// use shopify_function::map_enum;
+///
+/// enum Source { Standard, Express }
+/// enum Target { Standard, Express }
+///
+/// map_enum!(Source => Target {
+///     Standard => Standard,
+/// });
+/// ```
+#[macro_export]
+macro_rules! map_enum {
+    (
+        $source:ty => $target:ty {
+            $($from:ident $(($($binding:ident),*))? => $to:ident $(($($value:expr),*))?),* $(,)?
+        }
+    ) => {
+        impl ::std::convert::From<$source> for $target {
+            fn from(value: $source) -> Self {
+                type Source = $source;
+                match value {
+                    $(Source::$from $(($($binding),*))? => Self::$to $(($($value),*))?,)*
+                }
+            }
+        }
+    };
+}
+
+/// Runs the function `f` with the invocation payload and asserts that its
+/// serialized output, as returned by [`output_bytes`], is at most `max_bytes`
+/// long. This macro is provided as a helper when writing tests, to guard
+/// against outputs growing past the Function's output size limit.
+///
+/// ```ignore
+/// assert_output_within!(function, include_str!("../fixtures/large_cart.json"), 20_000);
+/// ```
+#[macro_export]
+macro_rules! assert_output_within {
+    ($f:expr, $payload:expr, $max_bytes:expr $(,)?) => {{
+        let output = $crate::run_function_with_input($f, $payload).expect("Error running function");
+        let bytes = $crate::output_bytes(&output).expect("Error serializing output");
+        let max_bytes: usize = $max_bytes;
+        assert!(
+            bytes.len() <= max_bytes,
+            "Function output is {} bytes, exceeding the budget of {} bytes",
+            bytes.len(),
+            max_bytes
+        );
+    }};
+}
+
+/// Asserts at compile time that the function `f` takes the given input type,
+/// e.g. to catch a function drifting from the target it was written for.
+///
+/// ```ignore
+/// assert_input_type!(function, input::ResponseData);
+/// ```
+#[macro_export]
+macro_rules! assert_input_type {
+    ($f:path, $input:ty $(,)?) => {
+        const _: () = {
+            let _: fn($input) -> _ = $f;
+        };
+    };
+}
+
+/// Asserts that two [`scalars::Decimal`] values are equal within `epsilon`,
+/// using [`scalars::Decimal::approx_eq`]. The default `epsilon` is `1e-9`.
+///
+/// ```ignore
+/// assert_decimal_eq!(Decimal(10.0 / 3.0 * 3.0), Decimal(10.0));
+/// assert_decimal_eq!(discount.value, Decimal(33.33), 0.01);
+/// ```
+#[macro_export]
+macro_rules! assert_decimal_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_decimal_eq!($left, $right, 1e-9)
+    };
+    ($left:expr, $right:expr, $epsilon:expr $(,)?) => {{
+        let left: $crate::scalars::Decimal = $left;
+        let right: $crate::scalars::Decimal = $right;
+        let epsilon: f64 = $epsilon;
+        assert!(
+            left.approx_eq(&right, epsilon),
+            "assertion `left == right` failed (epsilon: {})\n  left: {:?}\n right: {:?}",
+            epsilon,
+            left,
+            right
+        );
+    }};
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[derive(serde::Deserialize, Debug, PartialEq)]
+    struct Config {
+        quantity: i64,
+    }
+
+    fn run(config: Config) -> Result<i64> {
+        Ok(config.quantity)
+    }
+
+    assert_input_type!(run, Config);
+
+    #[test]
+    fn test_shared_config_deserialization() -> Result<()> {
+        let payload = r#"{ "quantity": 5 }"#;
+
+        let rc = run_function_with_input(|config: Rc<Config>| Ok(config), payload)?;
+        let arc = run_function_with_input(|config: Arc<Config>| Ok(config), payload)?;
+
+        assert_eq!(*rc, Config { quantity: 5 });
+        assert_eq!(*arc, Config { quantity: 5 });
+        Ok(())
+    }
+
+    #[test]
+    fn test_output_bytes() -> Result<()> {
+        let output = serde_json::json!({ "name": "test", "quantity": 5 });
+
+        assert_eq!(output_bytes(&output)?, br#"{"name":"test","quantity":5}"#);
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_output_within() {
+        let payload = r#"{ "quantity": 5 }"#;
+
+        assert_output_within!(|config: Config| Ok(config.quantity), payload, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Function output is 2 bytes, exceeding the budget of 1 bytes")]
+    fn test_assert_output_within_exceeded() {
+        let payload = r#"{ "quantity": 10 }"#;
+
+        assert_output_within!(|config: Config| Ok(config.quantity), payload, 1);
+    }
+
+    #[test]
+    fn test_assert_decimal_eq() {
+        assert_decimal_eq!(scalars::Decimal(10.0 / 3.0 * 3.0), scalars::Decimal(10.0));
+        assert_decimal_eq!(scalars::Decimal(33.333), scalars::Decimal(33.33), 0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left == right` failed (epsilon: 0.01)")]
+    fn test_assert_decimal_eq_failure() {
+        assert_decimal_eq!(scalars::Decimal(1.0), scalars::Decimal(1.1), 0.01);
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn test_smallvec_round_trip() -> Result<()> {
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct Config {
+            tags: smallvec::SmallVec<[String; 2]>,
+        }
+
+        let payload = r#"{"tags":["a","b"]}"#;
+        let config = run_function_with_input(|config: Config| Ok(config), payload)?;
+
+        assert!(!config.tags.spilled());
+        assert_eq!(output_bytes(&config)?, payload.as_bytes());
+        Ok(())
+    }
+}
@@ -1,16 +1,51 @@
+mod bounded_string;
 mod decimal;
+mod email;
+mod phone_number;
 
 pub type Boolean = bool;
+/// Non-finite values are serialized as `null`, unlike [`Decimal`] which fails
+/// to serialize them.
 pub type Float = f64;
 pub type Int = i64;
 pub type ID = String;
 pub type JSON = serde_json::Value;
 pub use decimal::Decimal;
+pub use email::Email;
 pub type Void = ();
 pub type URL = String;
 pub type Handle = String;
+pub use phone_number::PhoneNumber;
 
 pub type Date = String;
 pub type DateTime = String;
 pub type DateTimeWithoutTimezone = String;
 pub type TimeWithoutTimezone = String;
+
+pub use bounded_string::BoundedString;
+
+/// Parsed from and formatted as the canonical hyphenated string. Requires the
+/// `uuid` feature.
+#[cfg(feature = "uuid")]
+pub type UUID = uuid::Uuid;
+
+#[cfg(all(test, feature = "uuid"))]
+mod tests {
+    use super::UUID;
+
+    #[test]
+    fn test_uuid_round_trip() {
+        let value = serde_json::json!("67e55044-10b1-426f-9247-bb680e5fe0c8");
+        let uuid: UUID = serde_json::from_value(value.clone()).expect("Error deserializing UUID");
+        assert_eq!(
+            value,
+            serde_json::to_value(uuid).expect("Error serializing UUID")
+        );
+    }
+
+    #[test]
+    fn test_uuid_deserialization_error() {
+        let value = serde_json::json!("not-a-uuid");
+        assert!(serde_json::from_value::<UUID>(value).is_err());
+    }
+}
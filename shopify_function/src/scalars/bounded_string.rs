@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
+
+/// A `String` of at most `MAX` characters, validated when deserialized.
+///
+/// Length is counted in Unicode scalar values (`char`s), not bytes.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct BoundedString<const MAX: usize>(String);
+
+impl<const MAX: usize> BoundedString<MAX> {
+    /// Access the value as a `&str`
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const MAX: usize> Deref for BoundedString<MAX> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const MAX: usize> fmt::Display for BoundedString<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<const MAX: usize> TryFrom<String> for BoundedString<MAX> {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.chars().count() > MAX {
+            return Err(format!(
+                "Error parsing string: length must be at most {MAX} characters"
+            ));
+        }
+        Ok(Self(value))
+    }
+}
+
+impl<const MAX: usize> TryFrom<&str> for BoundedString<MAX> {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.to_string().try_into()
+    }
+}
+
+impl<const MAX: usize> From<BoundedString<MAX>> for String {
+    fn from(value: BoundedString<MAX>) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BoundedString;
+
+    #[test]
+    fn test_json_round_trip() {
+        let value = serde_json::json!("héllo");
+        let string: BoundedString<5> =
+            serde_json::from_value(value.clone()).expect("Error deserializing from JSON");
+        assert_eq!("héllo", string.as_str());
+        assert_eq!(
+            value,
+            serde_json::to_value(string).expect("Error serializing to JSON")
+        );
+    }
+
+    #[test]
+    fn test_json_deserialization_error() {
+        let error = serde_json::from_value::<BoundedString<4>>(serde_json::json!("hello"))
+            .expect_err("Expected an error");
+        assert_eq!(
+            "Error parsing string: length must be at most 4 characters",
+            error.to_string()
+        );
+    }
+}
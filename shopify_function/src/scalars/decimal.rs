@@ -1,11 +1,22 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use std::cmp::Ordering;
 use std::ops::Deref;
 
 /// Convenience wrapper for converting between Shopify's `Decimal` scalar, which
 /// is serialized as a `String`, and Rust's `f64`.
-#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Copy)]
+///
+/// `==`, `<` and the other comparison operators behave like they do for `f64`,
+/// so `NaN` isn't equal to itself. [`Ord`], which makes `Decimal` usable with
+/// `sort`, `min` and `max`, follows [`f64::total_cmp`] except that `-0.0` and
+/// `0.0` are equal, and orders `NaN` after every other value. The two only
+/// disagree on `NaN`, which Shopify never sends as a `Decimal`.
+///
+/// JSON can't represent `NaN` or infinities, so serializing a non-finite
+/// `Decimal` returns an error rather than emitting an invalid value. Only
+/// `Decimal` is checked: `Float` fields are plain `f64`s, which `serde_json`
+/// serializes as `null` when non-finite.
+#[derive(Deserialize, Debug, Clone, Copy)]
 #[serde(try_from = "String")]
-#[serde(into = "String")]
 pub struct Decimal(pub f64);
 
 impl Decimal {
@@ -13,6 +24,26 @@ impl Decimal {
     pub fn as_f64(&self) -> f64 {
         self.0
     }
+
+    /// Whether the two values differ by at most `epsilon`, to compare results
+    /// of float arithmetic such as `10.0 / 3.0 * 3.0`.
+    pub fn approx_eq(&self, other: &Decimal, epsilon: f64) -> bool {
+        self.0 == other.0 || (self.0 - other.0).abs() <= epsilon
+    }
+
+    /// Builds a decimal from an integer amount of minor units, e.g. cents with
+    /// a `scale` of 2: `Decimal::from_minor_units(1050, 2)` is `10.5`.
+    pub fn from_minor_units(units: i64, scale: u32) -> Self {
+        Self(units as f64 / 10f64.powi(scale as i32))
+    }
+
+    /// Converts the decimal to an integer amount of minor units, e.g. cents
+    /// with a `scale` of 2. The value is rounded to the nearest unit, with
+    /// halfway cases rounded away from zero, and saturates at the bounds of
+    /// `i64`. `NaN` converts to `0`.
+    pub fn to_minor_units(&self, scale: u32) -> i64 {
+        (self.0 * 10f64.powi(scale as i32)).round() as i64
+    }
 }
 
 impl Deref for Decimal {
@@ -23,6 +54,41 @@ impl Deref for Decimal {
     }
 }
 
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Decimal {}
+
+// Keeps the comparison operators consistent with `f64` rather than `Ord`.
+#[allow(clippy::non_canonical_partial_ord_impl)]
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // `-0.0 == 0.0` is true, so this normalizes `-0.0` to `0.0`.
+        let normalize = |value: f64| if value == 0.0 { 0.0 } else { value };
+        normalize(self.0).total_cmp(&normalize(other.0))
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !self.0.is_finite() {
+            return Err(serde::ser::Error::custom(
+                "Error serializing decimal: value must be finite",
+            ));
+        }
+        serializer.serialize_str(ryu::Buffer::new().format_finite(self.0))
+    }
+}
+
 impl TryFrom<String> for Decimal {
     type Error = &'static str;
 
@@ -80,4 +146,75 @@ mod tests {
         let json_value = serde_json::to_value(decimal).expect("Error serializing to JSON");
         assert_eq!(serde_json::json!("123.4"), json_value);
     }
+
+    #[test]
+    fn test_json_serialization_non_finite_error() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let error = serde_json::to_value(Decimal(value)).expect_err("Expected an error");
+            assert_eq!(
+                "Error serializing decimal: value must be finite",
+                error.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        assert!(Decimal(10.0 / 3.0 * 3.0).approx_eq(&Decimal(10.0), 1e-9));
+        assert!(Decimal(1.0).approx_eq(&Decimal(1.05), 0.1));
+        assert!(!Decimal(1.0).approx_eq(&Decimal(1.2), 0.1));
+        assert!(Decimal(f64::INFINITY).approx_eq(&Decimal(f64::INFINITY), 0.0));
+        assert!(!Decimal(f64::NAN).approx_eq(&Decimal(f64::NAN), 1.0));
+    }
+
+    #[test]
+    fn test_minor_units() {
+        assert_eq!(Decimal(10.5), Decimal::from_minor_units(1050, 2));
+        assert_eq!(Decimal(-0.07), Decimal::from_minor_units(-7, 2));
+        assert_eq!(Decimal(42.0), Decimal::from_minor_units(42, 0));
+        assert_eq!(1050, Decimal(10.5).to_minor_units(2));
+        assert_eq!(30, Decimal(0.1 + 0.2).to_minor_units(2));
+        assert_eq!(13, Decimal(0.125).to_minor_units(2));
+        assert_eq!(-13, Decimal(-0.125).to_minor_units(2));
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut decimals = vec![Decimal(2.5), Decimal(-1.0), Decimal(0.0), Decimal(-3.25)];
+        decimals.sort();
+        assert_eq!(
+            vec![Decimal(-3.25), Decimal(-1.0), Decimal(0.0), Decimal(2.5)],
+            decimals
+        );
+        assert_eq!(Some(&Decimal(2.5)), decimals.iter().max());
+        assert_eq!(Some(&Decimal(-3.25)), decimals.iter().min());
+    }
+
+    #[test]
+    fn test_ordering_equal_values() {
+        assert_eq!(std::cmp::Ordering::Equal, Decimal(1.5).cmp(&Decimal(1.5)));
+        assert!(Decimal(1.5) <= Decimal(1.5));
+        assert!(Decimal(1.5) >= Decimal(1.5));
+    }
+
+    #[test]
+    fn test_nan_comparisons() {
+        assert_ne!(Decimal(f64::NAN), Decimal(f64::NAN));
+        assert_eq!(None, Decimal(f64::NAN).partial_cmp(&Decimal(1.0)));
+        assert_eq!(
+            std::cmp::Ordering::Greater,
+            Decimal(f64::NAN).cmp(&Decimal(f64::INFINITY))
+        );
+        assert_eq!(
+            std::cmp::Ordering::Equal,
+            Decimal(f64::NAN).cmp(&Decimal(f64::NAN))
+        );
+    }
+
+    #[test]
+    fn test_signed_zeros_are_equal() {
+        assert_eq!(Decimal(0.0), Decimal(-0.0));
+        assert_eq!(std::cmp::Ordering::Equal, Decimal(-0.0).cmp(&Decimal(0.0)));
+        assert!(Decimal(-0.0) > Decimal(-f64::MIN_POSITIVE));
+    }
 }
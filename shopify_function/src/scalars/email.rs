@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
+
+/// An email address, validated when deserialized.
+///
+/// Validation is intentionally permissive: the value must contain no
+/// whitespace, a non-empty local part before the last `@`, and a domain
+/// containing a `.` that doesn't start or end with one.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct Email(String);
+
+impl Email {
+    /// Access the address as a `&str`
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The part of the address after the `@`
+    pub fn domain(&self) -> &str {
+        self.0.rsplit_once('@').map_or("", |(_, domain)| domain)
+    }
+}
+
+impl Deref for Email {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for Email {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for Email {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.chars().any(char::is_whitespace) {
+            return Err("Error parsing email: must not contain whitespace");
+        }
+        let Some((local, domain)) = value.rsplit_once('@') else {
+            return Err("Error parsing email: missing @");
+        };
+        if local.is_empty() {
+            return Err("Error parsing email: missing local part");
+        }
+        if !domain.contains('.') || domain.starts_with('.') || domain.ends_with('.') {
+            return Err("Error parsing email: invalid domain");
+        }
+        Ok(Self(value))
+    }
+}
+
+impl From<Email> for String {
+    fn from(value: Email) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Email;
+
+    #[test]
+    fn test_json_round_trip() {
+        let value = serde_json::json!("jane.doe+promo@example.co.uk");
+        let email: Email =
+            serde_json::from_value(value.clone()).expect("Error deserializing from JSON");
+        assert_eq!("example.co.uk", email.domain());
+        assert_eq!(
+            value,
+            serde_json::to_value(email).expect("Error serializing to JSON")
+        );
+    }
+
+    #[test]
+    fn test_json_deserialization_error() {
+        for (value, message) in [
+            ("jane.doe", "Error parsing email: missing @"),
+            ("@example.com", "Error parsing email: missing local part"),
+            ("jane@localhost", "Error parsing email: invalid domain"),
+            ("jane@example.", "Error parsing email: invalid domain"),
+            (
+                "jane doe@example.com",
+                "Error parsing email: must not contain whitespace",
+            ),
+        ] {
+            let error = serde_json::from_value::<Email>(serde_json::json!(value))
+                .expect_err("Expected an error");
+            assert_eq!(message, error.to_string());
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
+
+/// A phone number, validated when deserialized.
+///
+/// Validation is intentionally permissive: an optional leading `+` followed by
+/// 7 to 15 digits, as in E.164, which may be grouped with spaces, `-`, `.` or
+/// parentheses. The original formatting is preserved.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(try_from = "String", into = "String")]
+pub struct PhoneNumber(String);
+
+impl PhoneNumber {
+    /// Access the number as a `&str`
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The digits of the number, without the leading `+` or separators
+    pub fn digits(&self) -> String {
+        self.0.chars().filter(char::is_ascii_digit).collect()
+    }
+}
+
+impl Deref for PhoneNumber {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for PhoneNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for PhoneNumber {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let number = value.strip_prefix('+').unwrap_or(&value);
+        if !number
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        {
+            return Err("Error parsing phone number: invalid character");
+        }
+        let digits = number.chars().filter(char::is_ascii_digit).count();
+        if !(7..=15).contains(&digits) {
+            return Err("Error parsing phone number: must have between 7 and 15 digits");
+        }
+        Ok(Self(value))
+    }
+}
+
+impl From<PhoneNumber> for String {
+    fn from(value: PhoneNumber) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PhoneNumber;
+
+    #[test]
+    fn test_json_round_trip() {
+        let value = serde_json::json!("+1 (613) 555-0123");
+        let phone: PhoneNumber =
+            serde_json::from_value(value.clone()).expect("Error deserializing from JSON");
+        assert_eq!("16135550123", phone.digits());
+        assert_eq!(
+            value,
+            serde_json::to_value(phone).expect("Error serializing to JSON")
+        );
+    }
+
+    #[test]
+    fn test_json_deserialization_error() {
+        for (value, message) in [
+            ("call me", "Error parsing phone number: invalid character"),
+            (
+                "++16135550123",
+                "Error parsing phone number: invalid character",
+            ),
+            (
+                "555-01",
+                "Error parsing phone number: must have between 7 and 15 digits",
+            ),
+            (
+                "+1234567890123456",
+                "Error parsing phone number: must have between 7 and 15 digits",
+            ),
+        ] {
+            let error = serde_json::from_value::<PhoneNumber>(serde_json::json!(value))
+                .expect_err("Expected an error");
+            assert_eq!(message, error.to_string());
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Helpers to be used with serde's `#[serde(with = "...")]` field attribute,
+//! for values whose wire format differs from their natural Rust type.
+//!
+//! ```ignore
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     #[serde(with = "shopify_function::with::duration::seconds")]
+//!     pub timeout: std::time::Duration,
+//! }
+//! ```
+
+#[cfg(feature = "base64")]
+pub mod base64;
+pub mod duration;
+pub mod flexible_bool;
+pub mod singleton_or_vec;
+pub mod string_number;
@@ -0,0 +1,43 @@
+//! (De)serializes a `Vec<u8>` from a standard, padded base64 JSON string.
+//! Requires the `base64` feature.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(value: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&STANDARD.encode(value))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    STANDARD
+        .decode(encoded)
+        .map_err(|_| serde::de::Error::custom("Error parsing base64: invalid encoding"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Config {
+        #[serde(with = "super")]
+        token: Vec<u8>,
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let value = serde_json::json!({ "token": "aGVsbG8=" });
+        let config: Config = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(config.token, b"hello");
+        assert_eq!(serde_json::to_value(config).unwrap(), value);
+    }
+
+    #[test]
+    fn test_invalid_encoding() {
+        let error = serde_json::from_value::<Config>(serde_json::json!({ "token": "aGVsbG8" }))
+            .expect_err("Expected an error");
+        assert_eq!(error.to_string(), "Error parsing base64: invalid encoding");
+    }
+}
@@ -0,0 +1,95 @@
+//! (De)serializes a [`std::time::Duration`] from a non-negative JSON number.
+
+use serde::{Deserialize, Deserializer, Serializer};
+use std::time::Duration;
+
+/// Interprets the JSON number as a (possibly fractional) number of seconds.
+pub mod seconds {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.subsec_nanos() == 0 {
+            serializer.serialize_u64(value.as_secs())
+        } else {
+            serializer.serialize_f64(value.as_secs_f64())
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let secs = f64::deserialize(deserializer)?;
+        from_secs_f64(secs).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Interprets the JSON number as a (possibly fractional) number of milliseconds.
+pub mod milliseconds {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        if value.subsec_nanos() == value.subsec_millis() * 1_000_000 {
+            serializer.serialize_u64(value.as_millis() as u64)
+        } else {
+            serializer.serialize_f64(value.as_secs_f64() * 1000.0)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let millis = f64::deserialize(deserializer)?;
+        from_secs_f64(millis / 1000.0).map_err(serde::de::Error::custom)
+    }
+}
+
+fn from_secs_f64(secs: f64) -> Result<Duration, &'static str> {
+    if !secs.is_finite() {
+        return Err("Error parsing duration: value must be finite");
+    }
+    if secs < 0.0 {
+        return Err("Error parsing duration: value must not be negative");
+    }
+    Duration::try_from_secs_f64(secs).map_err(|_| "Error parsing duration: value is out of range")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Config {
+        #[serde(with = "super::seconds")]
+        timeout: Duration,
+        #[serde(with = "super::milliseconds")]
+        delay: Duration,
+    }
+
+    #[test]
+    fn test_json_deserialization() {
+        let value = serde_json::json!({ "timeout": 1.5, "delay": 250 });
+        let config: Config = serde_json::from_value(value).expect("Error deserializing from JSON");
+        assert_eq!(Duration::from_millis(1500), config.timeout);
+        assert_eq!(Duration::from_millis(250), config.delay);
+    }
+
+    #[test]
+    fn test_json_deserialization_negative_error() {
+        let value = serde_json::json!({ "timeout": -1, "delay": 0 });
+        let error = serde_json::from_value::<Config>(value).expect_err("Expected an error");
+        assert_eq!(
+            "Error parsing duration: value must not be negative",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_json_serialization() {
+        let config = Config {
+            timeout: Duration::from_secs(30),
+            delay: Duration::from_micros(1500),
+        };
+        let json_value = serde_json::to_value(config).expect("Error serializing to JSON");
+        assert_eq!(
+            serde_json::json!({ "timeout": 30, "delay": 1.5 }),
+            json_value
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Deserializes a `bool` from a JSON boolean, the strings `"true"`/`"false"`,
+//! or the numbers `1`/`0`. Always serializes as a boolean.
+
+use serde::de::{self, Visitor};
+use serde::{Deserializer, Serializer};
+use std::fmt;
+
+const ERROR: &str = "Error parsing bool: expected true, false, \"true\", \"false\", 1 or 0";
+
+pub fn serialize<S: Serializer>(value: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bool(*value)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    deserializer.deserialize_any(FlexibleBoolVisitor)
+}
+
+struct FlexibleBoolVisitor;
+
+impl Visitor<'_> for FlexibleBoolVisitor {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a boolean, \"true\", \"false\", 1 or 0")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<bool, E> {
+        Ok(value)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<bool, E> {
+        match value {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            _ => Err(E::custom(ERROR)),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<bool, E> {
+        match value {
+            1 => Ok(true),
+            0 => Ok(false),
+            _ => Err(E::custom(ERROR)),
+        }
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<bool, E> {
+        match value {
+            1 => Ok(true),
+            0 => Ok(false),
+            _ => Err(E::custom(ERROR)),
+        }
+    }
+
+    fn visit_f64<E: de::Error>(self, _value: f64) -> Result<bool, E> {
+        Err(E::custom(ERROR))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<bool, E> {
+        Err(E::custom(ERROR))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Config {
+        #[serde(with = "super")]
+        enabled: bool,
+    }
+
+    #[test]
+    fn test_json_deserialization() {
+        for (value, expected) in [
+            (serde_json::json!(true), true),
+            (serde_json::json!(false), false),
+            (serde_json::json!("true"), true),
+            (serde_json::json!("false"), false),
+            (serde_json::json!(1), true),
+            (serde_json::json!(0), false),
+        ] {
+            let config: Config = serde_json::from_value(serde_json::json!({ "enabled": value }))
+                .expect("Error deserializing from JSON");
+            assert_eq!(expected, config.enabled);
+        }
+    }
+
+    #[test]
+    fn test_json_deserialization_error() {
+        for value in [
+            serde_json::json!("yes"),
+            serde_json::json!(2),
+            serde_json::json!(-1),
+            serde_json::json!(1.0),
+            serde_json::json!(null),
+        ] {
+            let error = serde_json::from_value::<Config>(serde_json::json!({ "enabled": value }))
+                .expect_err("Expected an error");
+            assert_eq!(
+                "Error parsing bool: expected true, false, \"true\", \"false\", 1 or 0",
+                error.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_json_serialization() {
+        let json_value =
+            serde_json::to_value(Config { enabled: true }).expect("Error serializing to JSON");
+        assert_eq!(serde_json::json!({ "enabled": true }), json_value);
+    }
+}
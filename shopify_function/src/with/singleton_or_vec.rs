@@ -0,0 +1,57 @@
+//! Deserializes a `Vec<T>` from either a JSON array or a single value, which
+//! is wrapped into a one-element `Vec`. Always serializes as an array.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SingletonOrVec<T> {
+    Vec(Vec<T>),
+    Singleton(T),
+}
+
+pub fn serialize<T: Serialize, S: Serializer>(
+    value: &[T],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.serialize(serializer)
+}
+
+pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<T>, D::Error> {
+    Ok(match SingletonOrVec::deserialize(deserializer)? {
+        SingletonOrVec::Vec(values) => values,
+        SingletonOrVec::Singleton(value) => vec![value],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Config {
+        #[serde(with = "super")]
+        ids: Vec<String>,
+    }
+
+    #[test]
+    fn test_json_deserialization() {
+        let single: Config = serde_json::from_value(serde_json::json!({ "ids": "a" }))
+            .expect("Error deserializing from JSON");
+        let many: Config = serde_json::from_value(serde_json::json!({ "ids": ["a", "b"] }))
+            .expect("Error deserializing from JSON");
+        assert_eq!(vec!["a".to_string()], single.ids);
+        assert_eq!(vec!["a".to_string(), "b".to_string()], many.ids);
+    }
+
+    #[test]
+    fn test_json_serialization() {
+        let config = Config {
+            ids: vec!["a".to_string()],
+        };
+        let json_value = serde_json::to_value(config).expect("Error serializing to JSON");
+        assert_eq!(serde_json::json!({ "ids": ["a"] }), json_value);
+    }
+}
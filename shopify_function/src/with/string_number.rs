@@ -0,0 +1,63 @@
+//! (De)serializes a number from a JSON string, as Shopify encodes some
+//! numeric values, e.g. `"42"` for an `i64` or `"1.5"` for an `f64`. Works
+//! with any type implementing `FromStr` and `Display`.
+
+use serde::{Deserialize, Deserializer, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub fn serialize<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+pub fn deserialize<'de, T: FromStr, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    value
+        .parse()
+        .map_err(|_| serde::de::Error::custom("Error parsing number: invalid numeric string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize, Debug, PartialEq)]
+    struct Config {
+        #[serde(with = "super")]
+        quantity: i64,
+        #[serde(with = "super")]
+        ratio: f64,
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let value = serde_json::json!({ "quantity": "42", "ratio": "1.5" });
+        let config: Config =
+            serde_json::from_value(value.clone()).expect("Error deserializing from JSON");
+        assert_eq!(
+            Config {
+                quantity: 42,
+                ratio: 1.5
+            },
+            config
+        );
+        assert_eq!(
+            value,
+            serde_json::to_value(config).expect("Error serializing to JSON")
+        );
+    }
+
+    #[test]
+    fn test_json_deserialization_error() {
+        for value in [
+            serde_json::json!({ "quantity": "4.2", "ratio": "1.5" }),
+            serde_json::json!({ "quantity": "42", "ratio": "abc" }),
+        ] {
+            let error = serde_json::from_value::<Config>(value).expect_err("Expected an error");
+            assert_eq!(
+                "Error parsing number: invalid numeric string",
+                error.to_string()
+            );
+        }
+    }
+}
@@ -19,3 +19,630 @@ fn test_json_deserialization() {
     assert_eq!(parsed.num, Some(123));
     assert_eq!(parsed.name, Some("test".to_string()));
 }
+
+#[test]
+fn test_input_implements_graphql_query() {
+    use graphql_client::GraphQLQuery;
+
+    let body = Input::build_query(input::Variables);
+    let parsed: <Input as GraphQLQuery>::ResponseData =
+        serde_json::from_value(serde_json::json!({ "id": "gid://shopify/Order/1234567890" }))
+            .unwrap();
+
+    assert_eq!(body.operation_name, "Input");
+    assert_eq!(body.query, include_str!("fixtures/input.graphql"));
+    assert_eq!(parsed.id, "gid://shopify/Order/1234567890");
+}
+
+#[test]
+fn test_try_from_json_value() {
+    let parsed = input::ResponseData::try_from(serde_json::json!({
+        "id": "gid://shopify/Order/1234567890",
+        "num": 123
+    }))
+    .unwrap();
+
+    assert_eq!(parsed.id, "gid://shopify/Order/1234567890");
+    assert_eq!(parsed.num, Some(123));
+    assert_eq!(parsed.name, None);
+}
+
+#[test]
+fn test_into_json_value() {
+    let input = serde_json::json!({
+        "id": "gid://shopify/Order/1234567890",
+        "num": 123,
+        "name": "test",
+        "country": "CA"
+    });
+
+    let parsed = input::ResponseData::try_from(input.clone()).unwrap();
+
+    assert_eq!(serde_json::Value::from(parsed), input);
+}
+
+#[test]
+fn test_output_deserialization() {
+    let result: output::FunctionResult =
+        serde_json::from_value(serde_json::json!({ "name": "test", "country": "CA" })).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(result).unwrap(),
+        serde_json::json!({ "name": "test", "country": "CA" })
+    );
+}
+
+#[test]
+fn test_field_names() {
+    assert_eq!(
+        input::ResponseData::FIELD_NAMES,
+        ["id", "num", "name", "country"]
+    );
+    assert_eq!(output::FunctionResult::FIELD_NAMES, ["name", "country"]);
+}
+
+#[test]
+fn test_large_int_round_trip() {
+    let input =
+        r#"{"id":"gid://shopify/Order/1","num":9007199254740993,"name":"test","country":"CA"}"#;
+
+    let parsed: input::ResponseData = serde_json::from_str(input).unwrap();
+
+    assert_eq!(parsed.num, Some(9_007_199_254_740_993));
+    assert_eq!(serde_json::to_string(&parsed).unwrap(), input);
+}
+
+mod with_derives {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/input.graphql",
+        schema_path = "./tests/fixtures/schema.graphql",
+        derives = [Eq, ::std::hash::Hash, ::serde::Serialize]
+    );
+
+    #[test]
+    fn test_additional_derives() {
+        use std::collections::HashSet;
+
+        let result = output::FunctionResult {
+            name: Some("test".to_string()),
+            country: None,
+        };
+        let results: HashSet<_> = [result.clone(), result].into_iter().collect();
+
+        assert_eq!(results.len(), 1);
+    }
+}
+
+mod with_path_derives {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/discount.graphql",
+        schema_path = "./tests/fixtures/discount_schema.graphql",
+        derives = [::std::cmp::PartialOrd]
+    );
+
+    #[test]
+    fn test_path_derives_with_decimal_and_float_fields() {
+        let cheap: input::ResponseData =
+            serde_json::from_str(r#"{"id":"gid://shopify/Product/1","price":"1.5","weight":null}"#)
+                .unwrap();
+        let pricey = input::ResponseData {
+            price: Decimal(10.0),
+            ..cheap.clone()
+        };
+
+        assert!(cheap < pricey);
+        assert!(
+            output::FunctionResult {
+                price: None,
+                value: None,
+                target: None,
+            } < output::FunctionResult {
+                price: Some(Decimal(0.0)),
+                value: None,
+                target: None,
+            }
+        );
+    }
+}
+
+mod nullable_list {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/tags.graphql",
+        schema_path = "./tests/fixtures/schema.graphql"
+    );
+
+    #[test]
+    fn test_nullable_list_deserialization() {
+        let parse = |json: &str| serde_json::from_str::<input::ResponseData>(json).unwrap();
+
+        let absent = parse(r#"{ "id": "gid://shopify/Order/1" }"#);
+        let null = parse(r#"{ "id": "gid://shopify/Order/1", "tags": null }"#);
+        let empty = parse(r#"{ "id": "gid://shopify/Order/1", "tags": [] }"#);
+        let present = parse(r#"{ "id": "gid://shopify/Order/1", "tags": ["a"] }"#);
+
+        assert_eq!(absent.tags, None);
+        assert_eq!(null.tags, None);
+        assert_eq!(empty.tags, Some(vec![]));
+        assert_eq!(present.tags, Some(vec!["a".to_string()]));
+    }
+}
+
+mod with_schema_metadata {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/input.graphql",
+        schema_path = "./tests/fixtures/schema.graphql",
+        schema_docs = true,
+        embed_schema = true,
+        generated_types = true
+    );
+
+    #[test]
+    fn test_schema_docs() {
+        assert_eq!(
+            schema_docs::type_description("FunctionResult"),
+            Some("The result of the function.")
+        );
+        assert_eq!(
+            schema_docs::field_description("MutationRoot", "handleResult"),
+            Some("Handles the function result.")
+        );
+        assert_eq!(schema_docs::field_description("Input", "id"), None);
+    }
+
+    #[test]
+    fn test_generated_types() {
+        assert!(GENERATED_TYPES.contains(&"input::ResponseData"));
+        assert!(GENERATED_TYPES.contains(&"output::FunctionResult"));
+        assert!(!GENERATED_TYPES.contains(&"Input"));
+    }
+
+    #[test]
+    fn test_embedded_schema() {
+        assert_eq!(SCHEMA_SDL, include_str!("./fixtures/schema.graphql"));
+    }
+}
+
+mod recursive_input {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/id.graphql",
+        schema_path = "./tests/fixtures/recursive_schema.graphql"
+    );
+
+    #[test]
+    fn test_recursive_input_serialization() {
+        let tag = |tag: &str| output::Filter {
+            tag: Some(tag.to_string()),
+            not: Box::new(None),
+            and: Box::new(None),
+        };
+        let result = output::FunctionResult {
+            filter: Box::new(Some(output::Filter {
+                tag: None,
+                not: Box::new(Some(tag("a"))),
+                and: Box::new(Some(vec![tag("b"), tag("c")])),
+            })),
+        };
+
+        assert_eq!(
+            serde_json::to_value(result).unwrap(),
+            serde_json::json!({
+                "filter": {
+                    "not": { "tag": "a" },
+                    "and": [{ "tag": "b" }, { "tag": "c" }]
+                }
+            })
+        );
+    }
+}
+
+mod enum_mapping {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/input.graphql",
+        schema_path = "./tests/fixtures/schema.graphql",
+        extern_enums = []
+    );
+
+    map_enum!(input::CountryCode => output::CountryCode {
+        AC => AC,
+        CA => CA,
+        Other(code) => Other(code),
+    });
+
+    #[test]
+    fn test_map_enum() {
+        assert_eq!(
+            output::CountryCode::from(input::CountryCode::AC),
+            output::CountryCode::AC
+        );
+        assert_eq!(
+            output::CountryCode::from(input::CountryCode::CA),
+            output::CountryCode::CA
+        );
+        assert_eq!(
+            output::CountryCode::from(input::CountryCode::Other("ZZ".to_string())),
+            output::CountryCode::Other("ZZ".to_string())
+        );
+    }
+}
+
+mod strict_enums {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/input.graphql",
+        schema_path = "./tests/fixtures/schema.graphql",
+        extern_enums = [],
+        strict_enums = true
+    );
+
+    #[test]
+    fn test_unknown_enum_value_error() {
+        let parsed = input::ResponseData::try_from(serde_json::json!({
+            "id": "gid://shopify/Order/1234567890",
+            "country": "CA"
+        }))
+        .unwrap();
+        assert_eq!(parsed.country, Some(input::CountryCode::CA));
+
+        let error = input::ResponseData::try_from(serde_json::json!({
+            "id": "gid://shopify/Order/1234567890",
+            "country": "ZZ"
+        }))
+        .expect_err("Expected an error");
+        assert_eq!(
+            error.to_string(),
+            "unknown variant `ZZ`, expected `AC` or `CA`"
+        );
+    }
+}
+
+mod enum_conversions {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/input.graphql",
+        schema_path = "./tests/fixtures/schema.graphql",
+        extern_enums = []
+    );
+
+    #[test]
+    fn test_enum_from_str() {
+        let country: input::CountryCode = "CA".into();
+        assert_eq!(country, input::CountryCode::CA);
+        assert_eq!(
+            output::CountryCode::from("ZZ"),
+            output::CountryCode::Other("ZZ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_enum_std_string_conversions() {
+        assert_eq!("CA".parse(), Ok(input::CountryCode::CA));
+        assert_eq!(
+            "ZZ".parse::<input::CountryCode>(),
+            Err(UnknownEnumValue("ZZ".to_string()))
+        );
+        assert_eq!(input::CountryCode::AC.as_ref(), "AC");
+        assert_eq!(input::CountryCode::Other("ZZ".to_string()).as_ref(), "ZZ");
+        assert_eq!(output::CountryCode::CA.to_string(), "CA");
+    }
+}
+
+mod connections {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/products.graphql",
+        schema_path = "./tests/fixtures/connection_schema.graphql",
+        flatten_connections = true
+    );
+
+    #[test]
+    fn test_connection_nodes() {
+        let input: input::ResponseData = serde_json::from_value(serde_json::json!({
+            "products": {
+                "edges": [
+                    { "node": { "id": "gid://shopify/Product/1" } },
+                    { "node": { "id": "gid://shopify/Product/2" } }
+                ]
+            }
+        }))
+        .unwrap();
+
+        let ids: Vec<&str> = input
+            .products
+            .nodes()
+            .map(|product| product.id.as_str())
+            .collect();
+        assert_eq!(ids, ["gid://shopify/Product/1", "gid://shopify/Product/2"]);
+    }
+}
+
+mod list_iterators {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/products.graphql",
+        schema_path = "./tests/fixtures/connection_schema.graphql",
+        list_iterators = true
+    );
+
+    #[test]
+    fn test_list_iterators() {
+        let result = output::FunctionResult {
+            product_ids: vec!["1".to_string(), "2".to_string()],
+        };
+
+        assert_eq!(result.iter().count(), 2);
+        assert_eq!((&result).into_iter().next(), Some(&"1".to_string()));
+        assert_eq!(result.into_iter().collect::<Vec<_>>(), ["1", "2"]);
+    }
+
+    #[test]
+    fn test_renamed_field_names() {
+        assert_eq!(output::FunctionResult::FIELD_NAMES, ["productIds"]);
+    }
+}
+
+mod serialize_none_as_null {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/id.graphql",
+        schema_path = "./tests/fixtures/recursive_schema.graphql",
+        serialize_none_as_null = true
+    );
+
+    #[test]
+    fn test_none_serialized_as_null() {
+        let result = output::FunctionResult {
+            filter: Box::new(None),
+        };
+
+        assert_eq!(
+            serde_json::to_value(result).unwrap(),
+            serde_json::json!({ "filter": null })
+        );
+    }
+}
+
+mod shared_fragments {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/with_fragment.graphql",
+        schema_path = "./tests/fixtures/schema.graphql",
+        fragments = "./tests/fixtures/fragments.graphql"
+    );
+
+    #[test]
+    fn test_fragment_fields() {
+        let input: input::ResponseData = serde_json::from_value(serde_json::json!({
+            "id": "gid://shopify/Order/1234567890",
+            "num": 123,
+            "name": "test"
+        }))
+        .unwrap();
+
+        assert_eq!(input.order_fields.id, "gid://shopify/Order/1234567890");
+        assert_eq!(input.order_fields.num, Some(123));
+        assert_eq!(input.name, Some("test".to_string()));
+    }
+}
+
+mod crate_visibility {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/input.graphql",
+        schema_path = "./tests/fixtures/schema.graphql",
+        visibility = "pub(crate)"
+    );
+
+    #[test]
+    fn test_crate_visible_types() {
+        let parsed = input::ResponseData::try_from(serde_json::json!({
+            "id": "gid://shopify/Order/1234567890"
+        }))
+        .unwrap();
+
+        assert_eq!(parsed.id, "gid://shopify/Order/1234567890");
+    }
+}
+
+mod conditional_fields {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/conditional.graphql",
+        schema_path = "./tests/fixtures/schema.graphql"
+    );
+
+    #[test]
+    fn test_excluded_fields_deserialize_as_none() {
+        let parsed: input::ResponseData = serde_json::from_value(serde_json::json!({})).unwrap();
+
+        assert_eq!(parsed.id, None);
+        assert_eq!(parsed.num, None);
+        assert_eq!(parsed.name, None);
+    }
+
+    #[test]
+    fn test_included_fields() {
+        let parsed: input::ResponseData = serde_json::from_value(serde_json::json!({
+            "id": "gid://shopify/Order/1234567890",
+            "num": 123
+        }))
+        .unwrap();
+
+        assert_eq!(
+            parsed.id,
+            Some("gid://shopify/Order/1234567890".to_string())
+        );
+        assert_eq!(parsed.num, Some(123));
+    }
+}
+
+mod one_of_constructors {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/discount.graphql",
+        schema_path = "./tests/fixtures/discount_schema.graphql"
+    );
+
+    #[test]
+    fn test_variant_constructors() {
+        let percentage = output::Value::percentage(Decimal(10.0));
+        let fixed_amount = output::Value::fixed_amount(Decimal(5.0), Some(true));
+
+        assert_eq!(
+            serde_json::to_value(&percentage).unwrap(),
+            serde_json::json!({ "percentage": { "value": "10.0" } })
+        );
+        assert_eq!(
+            serde_json::to_value(&fixed_amount).unwrap(),
+            serde_json::json!({ "fixedAmount": { "amount": "5.0", "appliesToEachItem": true } })
+        );
+    }
+
+    #[test]
+    fn test_try_from_input_object_variant() {
+        let target =
+            output::Target::product_variant("gid://shopify/ProductVariant/1".to_string(), None);
+        let variant: &output::ProductVariantTarget = (&target).try_into().unwrap();
+        assert_eq!(variant.id, "gid://shopify/ProductVariant/1");
+
+        // `ID` and `Handle` both alias `String`, so they have no `TryFrom` impls.
+        let handle = output::Target::handle("shirt".to_string());
+        assert_eq!(
+            output::ProductVariantTarget::try_from(handle.clone()),
+            Err(handle)
+        );
+        assert_eq!(
+            serde_json::to_value(output::Target::id("gid://shopify/Product/1".to_string()))
+                .unwrap(),
+            serde_json::json!({ "id": "gid://shopify/Product/1" })
+        );
+    }
+
+    #[test]
+    fn test_keyword_variant_constructor() {
+        assert_eq!(
+            serde_json::to_value(output::Target::type_("shipping".to_string())).unwrap(),
+            serde_json::json!({ "type": "shipping" })
+        );
+    }
+}
+
+mod conditional_fragment_fields {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/conditional_fragments.graphql",
+        schema_path = "./tests/fixtures/discount_schema.graphql"
+    );
+
+    #[test]
+    fn test_excluded_fragment_fields_deserialize_as_none() {
+        let parsed: input::ResponseData = serde_json::from_value(serde_json::json!({
+            "merchandise": { "__typename": "ProductVariant", "title": "Shirt" }
+        }))
+        .unwrap();
+        let input::InputMerchandise::ProductVariant(variant) = parsed.merchandise else {
+            panic!("Expected a ProductVariant");
+        };
+        assert_eq!(variant.id, None);
+        assert_eq!(variant.title, "Shirt");
+
+        let parsed: input::ResponseData = serde_json::from_value(serde_json::json!({
+            "merchandise": { "__typename": "CustomProduct" }
+        }))
+        .unwrap();
+        let input::InputMerchandise::CustomProduct(custom_product) = parsed.merchandise else {
+            panic!("Expected a CustomProduct");
+        };
+        assert_eq!(custom_product.title, None);
+    }
+
+    #[test]
+    fn test_included_fragment_fields() {
+        let parsed: input::ResponseData = serde_json::from_value(serde_json::json!({
+            "merchandise": {
+                "__typename": "ProductVariant",
+                "id": "gid://shopify/ProductVariant/1",
+                "title": "Shirt"
+            }
+        }))
+        .unwrap();
+        let input::InputMerchandise::ProductVariant(variant) = parsed.merchandise else {
+            panic!("Expected a ProductVariant");
+        };
+        assert_eq!(
+            variant.id,
+            Some("gid://shopify/ProductVariant/1".to_string())
+        );
+    }
+}
+
+mod union_kinds {
+    use super::*;
+
+    generate_types!(
+        query_path = "./tests/fixtures/merchandise.graphql",
+        schema_path = "./tests/fixtures/discount_schema.graphql"
+    );
+
+    #[test]
+    fn test_kind() {
+        let input = serde_json::json!({
+            "merchandise": { "__typename": "ProductVariant", "id": "gid://shopify/ProductVariant/1" }
+        });
+        let parsed: input::ResponseData = serde_json::from_value(input.clone()).unwrap();
+
+        assert_eq!(parsed.merchandise.kind(), "ProductVariant");
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), input);
+
+        let parsed: input::ResponseData = serde_json::from_value(serde_json::json!({
+            "merchandise": { "__typename": "CustomProduct" }
+        }))
+        .unwrap();
+
+        assert_eq!(parsed.merchandise, input::InputMerchandise::CustomProduct);
+        assert_eq!(parsed.merchandise.kind(), "CustomProduct");
+    }
+
+    #[test]
+    fn test_unexpected_member_keeps_its_typename() {
+        let input = serde_json::json!({ "merchandise": { "__typename": "GiftCard" } });
+        let parsed: input::ResponseData = serde_json::from_value(input.clone()).unwrap();
+
+        assert_eq!(
+            parsed.merchandise,
+            input::InputMerchandise::Other("GiftCard".to_string())
+        );
+        assert_eq!(parsed.merchandise.kind(), "GiftCard");
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), input);
+    }
+
+    #[test]
+    fn test_missing_typename_error() {
+        let result = serde_json::from_value::<input::ResponseData>(serde_json::json!({
+            "merchandise": { "id": "gid://shopify/ProductVariant/1" }
+        }));
+
+        assert!(result.is_err());
+    }
+}
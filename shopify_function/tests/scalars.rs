@@ -0,0 +1,105 @@
+use serde::{de::DeserializeOwned, Serialize};
+use shopify_function::prelude::*;
+use std::fmt::Debug;
+
+/// Asserts that the JSON value deserializes into `T` and serializes back to
+/// the same JSON value.
+fn assert_json_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(
+    value: serde_json::Value,
+) {
+    let parsed: T = serde_json::from_value(value.clone())
+        .unwrap_or_else(|error| panic!("Error deserializing {value}: {error}"));
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+}
+
+/// Asserts that the value serializes to JSON and deserializes back to an
+/// equal value, for scalars whose JSON representation isn't canonical.
+fn assert_value_round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+    let json = serde_json::to_string(&value)
+        .unwrap_or_else(|error| panic!("Error serializing {value:?}: {error}"));
+    let parsed: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, value, "{json}");
+}
+
+#[test]
+fn test_boolean_round_trip() {
+    assert_json_round_trip::<Boolean>(serde_json::json!(true));
+    assert_json_round_trip::<Boolean>(serde_json::json!(false));
+}
+
+#[test]
+fn test_int_round_trip() {
+    for value in [0, 1, -1, i64::MAX, i64::MIN, 9_007_199_254_740_993] {
+        assert_json_round_trip::<Int>(serde_json::json!(value));
+    }
+}
+
+#[test]
+fn test_float_round_trip() {
+    for value in [
+        0.0,
+        -0.0,
+        1.5,
+        -1.5,
+        0.1 + 0.2,
+        1e21,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+    ] {
+        assert_value_round_trip::<Float>(value);
+    }
+}
+
+#[test]
+fn test_decimal_round_trip() {
+    for value in [0.0, -0.0, 123.4, -1.5, 0.1 + 0.2, 1e21, 1e-7, f64::MAX] {
+        assert_value_round_trip(Decimal(value));
+    }
+    assert_json_round_trip::<Decimal>(serde_json::json!("123.4"));
+    assert_json_round_trip::<Decimal>(serde_json::json!("-0.5"));
+}
+
+#[test]
+fn test_string_scalars_round_trip() {
+    for value in [
+        "",
+        "gid://shopify/Product/1",
+        "héllo wörld 👋",
+        "\"quoted\"\n",
+    ] {
+        assert_json_round_trip::<ID>(serde_json::json!(value));
+        assert_json_round_trip::<URL>(serde_json::json!(value));
+        assert_json_round_trip::<Handle>(serde_json::json!(value));
+        assert_json_round_trip::<Date>(serde_json::json!(value));
+        assert_json_round_trip::<DateTime>(serde_json::json!(value));
+    }
+}
+
+#[test]
+fn test_json_round_trip() {
+    assert_json_round_trip::<JSON>(serde_json::json!(null));
+    assert_json_round_trip::<JSON>(serde_json::json!({
+        "empty": "",
+        "unicode": "日本語",
+        "int": i64::MIN,
+        "uint": u64::MAX,
+        "float": -1.5,
+        "list": [true, null, {}],
+        "nested": { "deeper": [[]] }
+    }));
+}
+
+#[test]
+fn test_validated_scalars_round_trip() {
+    assert_json_round_trip::<Email>(serde_json::json!("jane@example.com"));
+    assert_json_round_trip::<PhoneNumber>(serde_json::json!("+1 613-555-0123"));
+    assert_json_round_trip::<BoundedString<3>>(serde_json::json!(""));
+    assert_json_round_trip::<BoundedString<3>>(serde_json::json!("日本語"));
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid_round_trip() {
+    assert_json_round_trip::<UUID>(serde_json::json!("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+    assert_json_round_trip::<UUID>(serde_json::json!("00000000-0000-0000-0000-000000000000"));
+}
@@ -7,7 +7,6 @@ const FUNCTION_INPUT: &str = r#"{
   "name": "test",
   "country": "CA"
 }"#;
-static mut FUNCTION_OUTPUT: Vec<u8> = vec![];
 
 generate_types!(
     query_path = "./tests/fixtures/input.graphql",
@@ -15,16 +14,18 @@ generate_types!(
 );
 
 #[test]
-fn test_function() {
+fn test_function() -> Result<()> {
     let expected_result = r#"{"name":"new name: gid://shopify/Order/1234567890","country":"CA"}"#;
-    main().unwrap();
-    let actual_result = std::str::from_utf8(unsafe { FUNCTION_OUTPUT.as_slice() }).unwrap();
-    assert_eq!(actual_result, expected_result);
+    main()?;
+    let output = shopify_function::run_function_with_input(my_function, FUNCTION_INPUT)?;
+    let actual_result = shopify_function::output_bytes(&output)?;
+    assert_eq!(std::str::from_utf8(&actual_result)?, expected_result);
+    Ok(())
 }
 
 #[shopify_function(
   input_stream = std::io::Cursor::new(FUNCTION_INPUT.as_bytes().to_vec()),
-  output_stream = unsafe { &mut FUNCTION_OUTPUT }
+  output_stream = std::io::sink()
 )]
 fn my_function(input: input::ResponseData) -> Result<output::FunctionResult> {
     Ok(output::FunctionResult {
@@ -7,14 +7,15 @@ const TARGET_A_INPUT: &str = r#"{
   "name": "test",
   "country": "CA"
 }"#;
-static mut TARGET_A_OUTPUT: Vec<u8> = vec![];
 
 #[test]
-fn test_target_a_export() {
+fn test_target_a_export() -> Result<()> {
     let expected_result = r#"{"status":200}"#;
     target_a::export();
-    let actual_result = std::str::from_utf8(unsafe { TARGET_A_OUTPUT.as_slice() }).unwrap();
-    assert_eq!(actual_result, expected_result);
+    let output = shopify_function::run_function_with_input(target_a::target_a, TARGET_A_INPUT)?;
+    let actual_result = shopify_function::output_bytes(&output)?;
+    assert_eq!(std::str::from_utf8(&actual_result)?, expected_result);
+    Ok(())
 }
 
 #[shopify_function_target(
@@ -22,7 +23,7 @@ fn test_target_a_export() {
   query_path = "./tests/fixtures/input.graphql",
   schema_path = "./tests/fixtures/schema_with_targets.graphql",
   input_stream = std::io::Cursor::new(TARGET_A_INPUT.as_bytes().to_vec()),
-  output_stream = unsafe { &mut TARGET_A_OUTPUT }
+  output_stream = std::io::sink()
 )]
 fn target_a(
     input: target_a::input::ResponseData,
@@ -37,14 +38,15 @@ const TARGET_B_INPUT: &str = r#"{
   "id": "gid://shopify/Order/1234567890",
   "targetAResult": 200
 }"#;
-static mut TARGET_B_OUTPUT: Vec<u8> = vec![];
 
 #[test]
-fn test_mod_b_export() {
+fn test_mod_b_export() -> Result<()> {
     let expected_result = r#"{"name":"new name: gid://shopify/Order/1234567890","country":"CA"}"#;
     mod_b::export();
-    let actual_result = std::str::from_utf8(unsafe { TARGET_B_OUTPUT.as_slice() }).unwrap();
-    assert_eq!(actual_result, expected_result);
+    let output = shopify_function::run_function_with_input(mod_b::some_function, TARGET_B_INPUT)?;
+    let actual_result = shopify_function::output_bytes(&output)?;
+    assert_eq!(std::str::from_utf8(&actual_result)?, expected_result);
+    Ok(())
 }
 
 #[shopify_function_target(
@@ -53,7 +55,7 @@ fn test_mod_b_export() {
   query_path = "./tests/fixtures/b.graphql",
   schema_path = "./tests/fixtures/schema_with_targets.graphql",
   input_stream = std::io::Cursor::new(TARGET_B_INPUT.as_bytes().to_vec()),
-  output_stream = unsafe { &mut TARGET_B_OUTPUT },
+  output_stream = std::io::sink(),
 )]
 fn some_function(
     input: mod_b::input::ResponseData,
@@ -86,3 +88,17 @@ fn _with_generated_country_code(
         status: Some(status),
     })
 }
+
+// Verify that the generated module can be kept out of the public API
+#[shopify_function_target(
+    target = "test.target-a",
+    module_name = "crate_visible",
+    query_path = "./tests/fixtures/input.graphql",
+    schema_path = "./tests/fixtures/schema_with_targets.graphql",
+    visibility = "pub(crate)"
+)]
+fn _with_crate_visibility(
+    _input: crate_visible::input::ResponseData,
+) -> Result<crate_visible::output::FunctionTargetAResult> {
+    Ok(crate_visible::output::FunctionTargetAResult { status: None })
+}
@@ -0,0 +1,164 @@
+use crate::{field_wire_name, type_name};
+use graphql_parser::query::{
+    Definition, Directive, OperationDefinition, Selection, SelectionSet, TypeCondition,
+};
+use std::collections::HashMap;
+use syn::{Fields, GenericArgument, Item, ItemStruct, PathArguments, Type};
+
+/// Makes the fields selected with an `@include` or `@skip` directive optional
+/// in the generated response types, since they're absent from the input when
+/// the directive excludes them. This covers the fields of inline fragments
+/// with such a directive too.
+///
+/// Fields selected through named fragments are left as they are.
+pub(crate) fn make_conditional_fields_optional(generated: &mut syn::File, query: &str) {
+    let document =
+        graphql_parser::parse_query::<&str>(query).expect("Error parsing the input query");
+    let Some(selection_set) = document
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::Operation(OperationDefinition::Query(query)) => Some(&query.selection_set),
+            Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+                Some(selection_set)
+            }
+            _ => None,
+        })
+    else {
+        return;
+    };
+
+    for module in generated.items.iter_mut() {
+        let Item::Mod(module) = module else {
+            continue;
+        };
+        let Some((_, items)) = module.content.as_mut() else {
+            continue;
+        };
+        let structs_by_name: HashMap<String, &ItemStruct> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Struct(item) => Some((item.ident.to_string(), item)),
+                _ => None,
+            })
+            .collect();
+        let mut conditional_fields = vec![];
+        collect_conditional_fields(
+            "ResponseData",
+            selection_set,
+            false,
+            &structs_by_name,
+            &mut conditional_fields,
+        );
+
+        for item in items.iter_mut() {
+            let Item::Struct(item) = item else {
+                continue;
+            };
+            let struct_name = item.ident.to_string();
+            let Fields::Named(fields) = &mut item.fields else {
+                continue;
+            };
+            for field in fields.named.iter_mut() {
+                let is_conditional = conditional_fields.iter().any(|(name, wire_name)| {
+                    *name == struct_name && *wire_name == field_wire_name(field)
+                });
+                if is_conditional && !is_option(&field.ty) {
+                    let ty = &field.ty;
+                    field.ty = syn::parse_quote!(Option<#ty>);
+                }
+            }
+        }
+    }
+}
+
+/// Collects the struct and field wire names of the conditionally selected
+/// fields. Inline fragments on a union or interface select the fields of the
+/// `{struct_name}On{type}` variant structs.
+fn collect_conditional_fields<'a>(
+    struct_name: &str,
+    selection_set: &SelectionSet<'a, &'a str>,
+    conditional: bool,
+    structs_by_name: &HashMap<String, &ItemStruct>,
+    conditional_fields: &mut Vec<(String, String)>,
+) {
+    let fields = match structs_by_name.get(struct_name).map(|item| &item.fields) {
+        Some(Fields::Named(fields)) => Some(fields),
+        _ => None,
+    };
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(selection) => {
+                let Some(fields) = fields else {
+                    continue;
+                };
+                let wire_name = selection.alias.unwrap_or(selection.name);
+                let Some(field) = fields
+                    .named
+                    .iter()
+                    .find(|field| field_wire_name(field) == wire_name)
+                else {
+                    continue;
+                };
+                if conditional || has_conditional_directive(&selection.directives) {
+                    conditional_fields.push((struct_name.to_string(), wire_name.to_string()));
+                }
+                if let Some(field_struct) = type_name(innermost_type(&field.ty)) {
+                    collect_conditional_fields(
+                        &field_struct,
+                        &selection.selection_set,
+                        false,
+                        structs_by_name,
+                        conditional_fields,
+                    );
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                let fragment_struct = fragment
+                    .type_condition
+                    .as_ref()
+                    .map(|TypeCondition::On(on)| format!("{struct_name}On{on}"))
+                    .filter(|name| structs_by_name.contains_key(name))
+                    .unwrap_or_else(|| struct_name.to_string());
+                collect_conditional_fields(
+                    &fragment_struct,
+                    &fragment.selection_set,
+                    conditional || has_conditional_directive(&fragment.directives),
+                    structs_by_name,
+                    conditional_fields,
+                );
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+fn has_conditional_directive<'a>(directives: &[Directive<'a, &'a str>]) -> bool {
+    directives
+        .iter()
+        .any(|directive| matches!(directive.name, "include" | "skip"))
+}
+
+fn is_option(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "Option"))
+}
+
+/// Returns `T` for `Option<T>`, `Vec<T>` or `Box<T>`, recursively.
+fn innermost_type(ty: &Type) -> &Type {
+    let Type::Path(path) = ty else {
+        return ty;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return ty;
+    };
+    if !matches!(segment.ident.to_string().as_str(), "Option" | "Vec" | "Box") {
+        return ty;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return ty;
+    };
+    match arguments.args.first() {
+        Some(GenericArgument::Type(inner)) => innermost_type(inner),
+        _ => ty,
+    }
+}
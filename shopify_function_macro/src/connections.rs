@@ -0,0 +1,50 @@
+use crate::{field_type, field_type_name, type_name, vec_item_type};
+use proc_macro2::{Ident, Span};
+use std::collections::HashMap;
+use syn::{Item, ItemStruct};
+
+/// Adds a `nodes()` accessor to the generated structs selecting the Relay
+/// connection shape `edges { node { ... } }`, iterating over the nodes without
+/// going through each edge.
+pub(crate) fn add_connection_accessors(generated: &mut syn::File) {
+    for module in generated.items.iter_mut() {
+        let Item::Mod(module) = module else {
+            continue;
+        };
+        let Some((_, items)) = module.content.as_mut() else {
+            continue;
+        };
+        let structs: Vec<&ItemStruct> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Struct(item) => Some(item),
+                _ => None,
+            })
+            .collect();
+        let structs_by_name: HashMap<String, &ItemStruct> = structs
+            .iter()
+            .map(|item| (item.ident.to_string(), *item))
+            .collect();
+
+        let impls: Vec<Item> = structs
+            .iter()
+            .filter_map(|item| {
+                let edge = field_type(item, "edges")
+                    .and_then(vec_item_type)
+                    .and_then(type_name)
+                    .and_then(|edge| structs_by_name.get(&edge))?;
+                let node = Ident::new(&field_type_name(edge, "node")?, Span::call_site());
+                let name = &item.ident;
+                Some(syn::parse_quote! {
+                    impl #name {
+                        /// Iterates over the `node` of each of the `edges`.
+                        pub fn nodes(&self) -> impl Iterator<Item = &#node> {
+                            self.edges.iter().map(|edge| &edge.node)
+                        }
+                    }
+                })
+            })
+            .collect();
+        items.extend(impls);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::field_type_name;
+use std::collections::HashMap;
+use syn::{Item, ItemStruct};
+
+/// Implements `shopify_function::CostTotalAmount` for the generated structs
+/// selecting `cost { totalAmount { amount } }`, such as cart lines.
+pub(crate) fn add_cost_impls(generated: &mut syn::File) {
+    for module in generated.items.iter_mut() {
+        let Item::Mod(module) = module else {
+            continue;
+        };
+        let Some((_, items)) = module.content.as_mut() else {
+            continue;
+        };
+        let structs: Vec<&ItemStruct> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Struct(item) => Some(item),
+                _ => None,
+            })
+            .collect();
+        let structs_by_name: HashMap<String, &ItemStruct> = structs
+            .iter()
+            .map(|item| (item.ident.to_string(), *item))
+            .collect();
+        let field_struct = |item: &ItemStruct, name: &str| {
+            field_type_name(item, name)
+                .and_then(|type_name| structs_by_name.get(&type_name).copied())
+        };
+
+        let impls: Vec<Item> = structs
+            .iter()
+            .filter(|item| {
+                field_struct(item, "cost")
+                    .and_then(|cost| field_struct(cost, "total_amount"))
+                    .and_then(|total_amount| field_type_name(total_amount, "amount"))
+                    .is_some_and(|amount| amount == "Decimal")
+            })
+            .map(|item| {
+                let name = &item.ident;
+                syn::parse_quote! {
+                    impl ::shopify_function::CostTotalAmount for #name {
+                        fn total_amount_decimal(&self) -> ::shopify_function::scalars::Decimal {
+                            self.cost.total_amount.amount
+                        }
+                    }
+                }
+            })
+            .collect();
+        items.extend(impls);
+    }
+}
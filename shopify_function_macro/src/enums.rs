@@ -0,0 +1,164 @@
+use crate::{type_name, RUST_KEYWORDS};
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::HashMap;
+use syn::{Fields, Item, ItemEnum};
+
+/// Adds conversion impls to the schema enums generated by graphql_client,
+/// i.e. enums made of unit variants and an `Other(String)` fallback: a lenient
+/// `From<&str>`, a strict `FromStr`, and `AsRef<str>` and `Display` returning
+/// the GraphQL value.
+pub(crate) fn add_enum_impls(generated: &mut syn::File) {
+    for module in generated.items.iter_mut() {
+        let Item::Mod(module) = module else {
+            continue;
+        };
+        let Some((_, items)) = module.content.as_mut() else {
+            continue;
+        };
+        let impls: Vec<Item> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Enum(item) if is_schema_enum(item) => Some(enum_impls(item)),
+                _ => None,
+            })
+            .flat_map(|tokens| {
+                syn::parse2::<syn::File>(tokens)
+                    .expect("Error parsing generated enum impls")
+                    .items
+            })
+            .collect();
+        items.extend(impls);
+    }
+}
+
+/// Replaces the `Deserialize` impls of the schema enums generated by
+/// graphql_client, which fall back to `Other` for unknown values, with impls
+/// failing on them.
+pub(crate) fn make_enums_strict(generated: &mut syn::File) {
+    for module in generated.items.iter_mut() {
+        let Item::Mod(module) = module else {
+            continue;
+        };
+        let Some((_, items)) = module.content.as_mut() else {
+            continue;
+        };
+        let strict_impls: HashMap<String, Item> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Enum(item) if is_schema_enum(item) => Some((
+                    item.ident.to_string(),
+                    syn::parse2(strict_deserialize_impl(item))
+                        .expect("Error parsing generated enum impls"),
+                )),
+                _ => None,
+            })
+            .collect();
+        for item in items.iter_mut() {
+            let Item::Impl(item_impl) = item else {
+                continue;
+            };
+            let is_deserialize = item_impl.trait_.as_ref().is_some_and(|(_, path, _)| {
+                path.segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Deserialize")
+            });
+            let strict_impl = type_name(&item_impl.self_ty)
+                .and_then(|name| strict_impls.get(&name))
+                .filter(|_| is_deserialize);
+            if let Some(strict_impl) = strict_impl {
+                *item = strict_impl.clone();
+            }
+        }
+    }
+}
+
+fn is_schema_enum(item: &ItemEnum) -> bool {
+    let has_other = item.variants.iter().any(|variant| {
+        variant.ident == "Other"
+            && matches!(&variant.fields, Fields::Unnamed(f) if f.unnamed.len() == 1)
+    });
+    let others_are_unit = item
+        .variants
+        .iter()
+        .filter(|variant| variant.ident != "Other")
+        .all(|variant| matches!(variant.fields, Fields::Unit));
+    has_other && others_are_unit
+}
+
+/// Returns the variant identifiers alongside the GraphQL enum values they map to.
+fn enum_values(item: &ItemEnum) -> (Vec<&syn::Ident>, Vec<String>) {
+    item.variants
+        .iter()
+        .filter(|variant| variant.ident != "Other")
+        .map(|variant| {
+            let name = variant.ident.to_string();
+            let value = match name.strip_suffix('_') {
+                Some(keyword) if RUST_KEYWORDS.contains(&keyword) => keyword.to_string(),
+                _ => name,
+            };
+            (&variant.ident, value)
+        })
+        .unzip()
+}
+
+fn strict_deserialize_impl(item: &ItemEnum) -> TokenStream {
+    let name = &item.ident;
+    let (variants, values) = enum_values(item);
+
+    quote! {
+        impl<'de> ::serde::Deserialize<'de> for #name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::std::result::Result<Self, D::Error> {
+                let value: String = ::serde::Deserialize::deserialize(deserializer)?;
+                match value.as_str() {
+                    #(#values => Ok(#name::#variants),)*
+                    _ => Err(::serde::de::Error::unknown_variant(&value, &[#(#values),*])),
+                }
+            }
+        }
+    }
+}
+
+fn enum_impls(item: &ItemEnum) -> TokenStream {
+    let name = &item.ident;
+    let (variants, values) = enum_values(item);
+
+    quote! {
+        impl ::std::convert::From<&str> for #name {
+            fn from(value: &str) -> Self {
+                match value {
+                    #(#values => #name::#variants,)*
+                    _ => #name::Other(value.to_string()),
+                }
+            }
+        }
+
+        impl ::std::str::FromStr for #name {
+            type Err = ::shopify_function::UnknownEnumValue;
+
+            fn from_str(value: &str) -> ::std::result::Result<Self, Self::Err> {
+                match value {
+                    #(#values => Ok(#name::#variants),)*
+                    _ => Err(::shopify_function::UnknownEnumValue(value.to_string())),
+                }
+            }
+        }
+
+        impl ::std::convert::AsRef<str> for #name {
+            fn as_ref(&self) -> &str {
+                match self {
+                    #(#name::#variants => #values,)*
+                    #name::Other(value) => value.as_str(),
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for #name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_ref())
+            }
+        }
+    }
+}
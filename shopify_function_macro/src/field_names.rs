@@ -0,0 +1,46 @@
+use crate::field_wire_name;
+use syn::{Fields, Item, ItemStruct};
+
+/// Adds a `FIELD_NAMES` constant to the generated structs, listing the
+/// GraphQL names of their fields. Fields flattened from fragments are left
+/// out, as they aren't GraphQL fields.
+pub(crate) fn add_field_names(generated: &mut syn::File) {
+    for module in generated.items.iter_mut() {
+        let Item::Mod(module) = module else {
+            continue;
+        };
+        let Some((_, items)) = module.content.as_mut() else {
+            continue;
+        };
+        let impls: Vec<Item> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Struct(item) => field_names_impl(item),
+                _ => None,
+            })
+            .collect();
+        items.extend(impls);
+    }
+}
+
+fn field_names_impl(item: &ItemStruct) -> Option<Item> {
+    let Fields::Named(fields) = &item.fields else {
+        return None;
+    };
+    let field_names = fields
+        .named
+        .iter()
+        .filter(|field| {
+            !field.attrs.iter().any(|attr| {
+                attr.path.is_ident("serde") && attr.tokens.to_string().contains("flatten")
+            })
+        })
+        .map(field_wire_name);
+    let name = &item.ident;
+    Some(syn::parse_quote! {
+        impl #name {
+            /// The GraphQL names of the fields, in declaration order.
+            pub const FIELD_NAMES: &'static [&'static str] = &[#(#field_names),*];
+        }
+    })
+}
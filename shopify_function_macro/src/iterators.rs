@@ -0,0 +1,71 @@
+use crate::vec_item_type;
+use syn::{Fields, Item, ItemStruct};
+
+/// Implements `IntoIterator` and an `iter()` method for the generated structs
+/// with exactly one list field, delegating to that field.
+pub(crate) fn add_iterator_impls(generated: &mut syn::File) {
+    for module in generated.items.iter_mut() {
+        let Item::Mod(module) = module else {
+            continue;
+        };
+        let Some((_, items)) = module.content.as_mut() else {
+            continue;
+        };
+        let impls: Vec<Item> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Struct(item) => iterator_impls(item),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        items.extend(impls);
+    }
+}
+
+fn iterator_impls(item: &ItemStruct) -> Option<Vec<Item>> {
+    let Fields::Named(fields) = &item.fields else {
+        return None;
+    };
+    let mut lists = fields
+        .named
+        .iter()
+        .filter_map(|field| Some((field.ident.as_ref()?, vec_item_type(&field.ty)?)));
+    let (field, ty) = lists.next()?;
+    if lists.next().is_some() {
+        return None;
+    }
+
+    let name = &item.ident;
+    let doc = format!(" Iterates over the `{field}`.");
+    Some(vec![
+        syn::parse_quote! {
+            impl #name {
+                #[doc = #doc]
+                pub fn iter(&self) -> ::std::slice::Iter<'_, #ty> {
+                    self.#field.iter()
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl ::std::iter::IntoIterator for #name {
+                type Item = #ty;
+                type IntoIter = ::std::vec::IntoIter<#ty>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    self.#field.into_iter()
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl<'a> ::std::iter::IntoIterator for &'a #name {
+                type Item = &'a #ty;
+                type IntoIter = ::std::slice::Iter<'a, #ty>;
+
+                fn into_iter(self) -> Self::IntoIter {
+                    self.#field.iter()
+                }
+            }
+        },
+    ])
+}
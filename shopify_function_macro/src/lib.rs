@@ -2,16 +2,27 @@ use convert_case::{Case, Casing};
 use graphql_client_codegen::{
     generate_module_token_stream_from_string, CodegenMode, GraphQLClientCodegenOptions,
 };
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{
     self,
     parse::{Parse, ParseStream},
-    parse_macro_input, Expr, ExprArray, FnArg, LitStr, Token,
+    parse_macro_input, Expr, ExprArray, FnArg, LitBool, LitStr, Token,
 };
 
+mod conditional;
+mod connections;
+mod cost;
+mod enums;
+mod field_names;
+mod iterators;
+mod one_of;
+mod schema_docs;
+mod unions;
+
 #[derive(Clone, Default)]
 struct ShopifyFunctionArgs {
     input_stream: Option<Expr>,
@@ -111,7 +122,7 @@ pub fn shopify_function(
             let input: #input_type = serde_json::from_str(&string)?;
             let mut out = #output_stream;
             let result = #name(input)?;
-            let serialized = serde_json::to_vec(&result)?;
+            let serialized = ::shopify_function::output_bytes(&result)?;
             std::io::Write::write_all(&mut out, serialized.as_slice())?;
             Ok(())
         }
@@ -130,6 +141,16 @@ struct ShopifyFunctionTargetArgs {
     input_stream: Option<Expr>,
     output_stream: Option<Expr>,
     extern_enums: Option<ExprArray>,
+    derives: Option<ExprArray>,
+    schema_docs: Option<LitBool>,
+    embed_schema: Option<LitBool>,
+    flatten_connections: Option<LitBool>,
+    generated_types: Option<LitBool>,
+    list_iterators: Option<LitBool>,
+    serialize_none_as_null: Option<LitBool>,
+    fragments: Option<LitStr>,
+    strict_enums: Option<LitBool>,
+    visibility: Option<LitStr>,
 }
 
 impl ShopifyFunctionTargetArgs {
@@ -165,6 +186,28 @@ impl Parse for ShopifyFunctionTargetArgs {
                 args.output_stream = Some(Self::parse::<kw::output_stream, Expr>(&input)?);
             } else if lookahead.peek(kw::extern_enums) {
                 args.extern_enums = Some(Self::parse::<kw::extern_enums, ExprArray>(&input)?);
+            } else if lookahead.peek(kw::derives) {
+                args.derives = Some(Self::parse::<kw::derives, ExprArray>(&input)?);
+            } else if lookahead.peek(kw::schema_docs) {
+                args.schema_docs = Some(Self::parse::<kw::schema_docs, LitBool>(&input)?);
+            } else if lookahead.peek(kw::embed_schema) {
+                args.embed_schema = Some(Self::parse::<kw::embed_schema, LitBool>(&input)?);
+            } else if lookahead.peek(kw::flatten_connections) {
+                args.flatten_connections =
+                    Some(Self::parse::<kw::flatten_connections, LitBool>(&input)?);
+            } else if lookahead.peek(kw::generated_types) {
+                args.generated_types = Some(Self::parse::<kw::generated_types, LitBool>(&input)?);
+            } else if lookahead.peek(kw::list_iterators) {
+                args.list_iterators = Some(Self::parse::<kw::list_iterators, LitBool>(&input)?);
+            } else if lookahead.peek(kw::serialize_none_as_null) {
+                args.serialize_none_as_null =
+                    Some(Self::parse::<kw::serialize_none_as_null, LitBool>(&input)?);
+            } else if lookahead.peek(kw::fragments) {
+                args.fragments = Some(Self::parse::<kw::fragments, LitStr>(&input)?);
+            } else if lookahead.peek(kw::strict_enums) {
+                args.strict_enums = Some(Self::parse::<kw::strict_enums, LitBool>(&input)?);
+            } else if lookahead.peek(kw::visibility) {
+                args.visibility = Some(Self::parse::<kw::visibility, LitStr>(&input)?);
             } else {
                 return Err(lookahead.error());
             }
@@ -180,6 +223,16 @@ struct GenerateTypeArgs {
     input_stream: Option<Expr>,
     output_stream: Option<Expr>,
     extern_enums: Option<ExprArray>,
+    derives: Option<ExprArray>,
+    schema_docs: Option<LitBool>,
+    embed_schema: Option<LitBool>,
+    flatten_connections: Option<LitBool>,
+    generated_types: Option<LitBool>,
+    list_iterators: Option<LitBool>,
+    serialize_none_as_null: Option<LitBool>,
+    fragments: Option<LitStr>,
+    strict_enums: Option<LitBool>,
+    visibility: Option<LitStr>,
 }
 
 impl GenerateTypeArgs {
@@ -211,6 +264,28 @@ impl Parse for GenerateTypeArgs {
                 args.output_stream = Some(Self::parse::<kw::output_stream, Expr>(&input)?);
             } else if lookahead.peek(kw::extern_enums) {
                 args.extern_enums = Some(Self::parse::<kw::extern_enums, ExprArray>(&input)?);
+            } else if lookahead.peek(kw::derives) {
+                args.derives = Some(Self::parse::<kw::derives, ExprArray>(&input)?);
+            } else if lookahead.peek(kw::schema_docs) {
+                args.schema_docs = Some(Self::parse::<kw::schema_docs, LitBool>(&input)?);
+            } else if lookahead.peek(kw::embed_schema) {
+                args.embed_schema = Some(Self::parse::<kw::embed_schema, LitBool>(&input)?);
+            } else if lookahead.peek(kw::flatten_connections) {
+                args.flatten_connections =
+                    Some(Self::parse::<kw::flatten_connections, LitBool>(&input)?);
+            } else if lookahead.peek(kw::generated_types) {
+                args.generated_types = Some(Self::parse::<kw::generated_types, LitBool>(&input)?);
+            } else if lookahead.peek(kw::list_iterators) {
+                args.list_iterators = Some(Self::parse::<kw::list_iterators, LitBool>(&input)?);
+            } else if lookahead.peek(kw::serialize_none_as_null) {
+                args.serialize_none_as_null =
+                    Some(Self::parse::<kw::serialize_none_as_null, LitBool>(&input)?);
+            } else if lookahead.peek(kw::fragments) {
+                args.fragments = Some(Self::parse::<kw::fragments, LitStr>(&input)?);
+            } else if lookahead.peek(kw::strict_enums) {
+                args.strict_enums = Some(Self::parse::<kw::strict_enums, LitBool>(&input)?);
+            } else if lookahead.peek(kw::visibility) {
+                args.visibility = Some(Self::parse::<kw::visibility, LitStr>(&input)?);
             } else {
                 return Err(lookahead.error());
             }
@@ -274,12 +349,17 @@ fn extract_shopify_function_return_type(ast: &syn::ItemFn) -> Result<&syn::Ident
 /// - Define a wrapper function that's exported to Wasm. The wrapper handles
 ///   decoding the input from STDIN, and encoding the output to STDOUT.
 ///
+/// The `input` and `output` modules are generated as with
+/// [`generate_types!`](macro@generate_types), so all their types implement
+/// `Clone`, `Debug`, `PartialEq`, `Deserialize` and `Serialize`, on top of any
+/// `derives`, and input and output types stay distinct.
 ///
 /// The macro takes the following parameters:
 /// - `query_path`: A path to a GraphQL query, whose result will be used
-///    as the input for the function invocation. The query MUST be named "Input".
+///   as the input for the function invocation. The query MUST be named "Input".
 /// - `schema_path`: A path to Shopify's GraphQL schema definition. Use the CLI
-///   to download a fresh copy.
+///   to download a fresh copy. Relative to the `SHOPIFY_SCHEMA_DIR` env var if
+///   set, which is itself relative to the crate's `Cargo.toml` unless absolute.
 /// - `target` (optional): The API-specific handle for the target if the function name does not match the target handle as `snake_case`
 /// - `module_name` (optional): The name of the generated module.
 ///   - default: The target handle as `snake_case`
@@ -288,6 +368,43 @@ fn extract_shopify_function_return_type(ast: &syn::ItemFn) -> Result<&syn::Ident
 ///   which can increase binary size, or for enums shared between multiple targets.
 ///   Example: `extern_enums = ["LanguageCode"]`
 ///    - default: `["LanguageCode", "CountryCode", "CurrencyCode"]`
+/// - `derives` (optional): A list of additional derive macros to apply to all
+///   generated types, on top of the default `Clone`, `Debug`, `PartialEq`,
+///   `Deserialize` and `Serialize`.
+///   Example: `derives = [::std::cmp::PartialOrd]`
+/// - `schema_docs` (optional): Whether to generate a `schema_docs` module exposing
+///   the type and field descriptions of the schema at runtime. This increases
+///   binary size when used.
+///    - default: `false`
+/// - `embed_schema` (optional): Whether to embed the schema SDL as a
+///   `SCHEMA_SDL` string constant. When used, this adds the full size of the
+///   schema file to the binary.
+///    - default: `false`
+/// - `flatten_connections` (optional): Whether to add a `nodes()` accessor to
+///   input types selecting the Relay connection shape `edges { node { ... } }`,
+///   iterating over the nodes directly.
+///    - default: `false`
+/// - `generated_types` (optional): Whether to generate a `GENERATED_TYPES`
+///   constant listing the paths of the generated types, such as
+///   `"input::ResponseData"`, to help finding them without expanding the macro.
+///    - default: `false`
+/// - `list_iterators` (optional): Whether to implement `IntoIterator` and an
+///   `iter()` method for the generated types with exactly one list field,
+///   delegating to that field.
+///    - default: `false`
+/// - `serialize_none_as_null` (optional): Whether to serialize `None` output
+///   fields as an explicit `null`, instead of omitting them.
+///    - default: `false`
+/// - `fragments` (optional): A path to a GraphQL file with fragment definitions
+///   shared between queries, which can then be spread in the query.
+///   Example: `fragments = "fragments.graphql"`
+/// - `strict_enums` (optional): Whether deserializing an input enum fails on
+///   values unknown to the schema, instead of falling back to `Other`.
+///    - default: `false`
+/// - `visibility` (optional): The visibility of the generated module, and of
+///   the re-export of the function next to it.
+///   Example: `visibility = "pub(crate)"`
+///    - default: `"pub"`
 #[proc_macro_attribute]
 pub fn shopify_function_target(
     attr: proc_macro::TokenStream,
@@ -296,6 +413,11 @@ pub fn shopify_function_target(
     let ast = parse_macro_input!(item as syn::ItemFn);
     let args = parse_macro_input!(attr as ShopifyFunctionTargetArgs);
 
+    let visibility = match parse_visibility(args.visibility.as_ref()) {
+        Ok(visibility) => visibility.unwrap_or_else(|| syn::parse_quote!(pub)),
+        Err(error) => return error.to_compile_error().into(),
+    };
+
     let function_name = &ast.sig.ident;
     let function_name_string = function_name.to_string();
     let target_handle_string = args.target.map_or(function_name_string.clone(), |target| {
@@ -325,11 +447,24 @@ pub fn shopify_function_target(
         .as_ref()
         .map(extract_extern_enums)
         .unwrap_or_else(default_exter_enums);
+    let derives = match args.derives.as_ref().map(extract_derives).transpose() {
+        Ok(derives) => derives.unwrap_or_default(),
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let typegen_options = TypegenOptions {
+        flatten_connections: flag(args.flatten_connections.as_ref()),
+        list_iterators: flag(args.list_iterators.as_ref()),
+        serialize_none_as_null: flag(args.serialize_none_as_null.as_ref()),
+        strict_enums: flag(args.strict_enums.as_ref()),
+    };
 
     let input_struct = generate_input_struct(
         query_path.as_str(),
         schema_path.as_str(),
         extern_enums.as_slice(),
+        derives.as_slice(),
+        args.fragments.as_ref().map(LitStr::value).as_deref(),
+        &typegen_options,
     );
 
     if let Err(error) = extract_shopify_function_return_type(&ast) {
@@ -344,12 +479,23 @@ pub fn shopify_function_target(
         output_result_type,
         &target_handle_string.to_case(Case::Camel)
     );
-    let output_struct =
-        generate_output_struct(&output_query, schema_path.as_str(), extern_enums.as_slice());
+    let output_struct = generate_output_struct(
+        &output_query,
+        schema_path.as_str(),
+        extern_enums.as_slice(),
+        derives.as_slice(),
+        &typegen_options,
+    );
 
     if let Err(error) = extract_shopify_function_return_type(&ast) {
         return error.to_compile_error().into();
     }
+    let schema_docs = generate_schema_docs_module(args.schema_docs.as_ref(), &schema_path);
+    let schema_sdl = generate_schema_sdl(args.embed_schema.as_ref(), &schema_path);
+    let generated_types = generate_type_list(
+        args.generated_types.as_ref(),
+        &[&input_struct, &output_struct],
+    );
 
     let input_stream = args
         .input_stream
@@ -363,12 +509,15 @@ pub fn shopify_function_target(
         });
 
     quote! {
-        pub mod #module_name {
+        #visibility mod #module_name {
             use super::*;
             use std::io::Write;
 
             #input_struct
             #output_struct
+            #schema_docs
+            #schema_sdl
+            #generated_types
 
             #[shopify_function(
                 input_stream = #input_stream,
@@ -382,7 +531,7 @@ pub fn shopify_function_target(
                 #output_stream.flush().unwrap();
             }
         }
-        pub use #module_name::#function_name;
+        #visibility use #module_name::#function_name;
     }
     .into()
 }
@@ -393,20 +542,73 @@ pub fn shopify_function_target(
 /// modules generate Rust types from the GraphQL schema file for the Function input
 /// and output respectively.
 ///
+/// All the generated types, in both modules, implement `Clone`, `Debug`,
+/// `PartialEq`, `Deserialize` and `Serialize`, on top of any `derives`. Input
+/// and output types stay distinct even when generated from the same schema
+/// type though, so echoing an input object in the output means converting it,
+/// e.g. field by field or through `serde_json::to_value` and `from_value`.
+///
+/// In test builds, `input::ResponseData` also converts from and to
+/// `serde_json::Value`, so test inputs can be written with `serde_json::json!`
+/// and dumped back to JSON for debugging.
+///
 /// The macro takes the following parameters:
 /// - `query_path`: A path to a GraphQL query, whose result will be used
-///    as the input for the function invocation. The query MUST be named "Input".
+///   as the input for the function invocation. The query MUST be named "Input".
 /// - `schema_path`: A path to Shopify's GraphQL schema definition. Use the CLI
-///   to download a fresh copy.
+///   to download a fresh copy. Relative to the `SHOPIFY_SCHEMA_DIR` env var if
+///   set, which is itself relative to the crate's `Cargo.toml` unless absolute.
 /// - `extern_enums` (optional): A list of Enums for which an external type should be used.
 ///   For those, code generation will be skipped. This is useful for large enums
 ///   which can increase binary size, or for enums shared between multiple targets.
 ///   Example: `extern_enums = ["LanguageCode"]`
 ///    - default: `["LanguageCode", "CountryCode", "CurrencyCode"]`
+/// - `derives` (optional): A list of additional derive macros to apply to all
+///   generated types, on top of the default `Clone`, `Debug`, `PartialEq`,
+///   `Deserialize` and `Serialize`.
+///   Example: `derives = [::std::cmp::PartialOrd]`
+/// - `schema_docs` (optional): Whether to generate a `schema_docs` module exposing
+///   the type and field descriptions of the schema at runtime. This increases
+///   binary size when used.
+///    - default: `false`
+/// - `embed_schema` (optional): Whether to embed the schema SDL as a
+///   `SCHEMA_SDL` string constant. When used, this adds the full size of the
+///   schema file to the binary.
+///    - default: `false`
+/// - `flatten_connections` (optional): Whether to add a `nodes()` accessor to
+///   input types selecting the Relay connection shape `edges { node { ... } }`,
+///   iterating over the nodes directly.
+///    - default: `false`
+/// - `generated_types` (optional): Whether to generate a `GENERATED_TYPES`
+///   constant listing the paths of the generated types, such as
+///   `"input::ResponseData"`, to help finding them without expanding the macro.
+///    - default: `false`
+/// - `list_iterators` (optional): Whether to implement `IntoIterator` and an
+///   `iter()` method for the generated types with exactly one list field,
+///   delegating to that field.
+///    - default: `false`
+/// - `serialize_none_as_null` (optional): Whether to serialize `None` output
+///   fields as an explicit `null`, instead of omitting them.
+///    - default: `false`
+/// - `fragments` (optional): A path to a GraphQL file with fragment definitions
+///   shared between queries, which can then be spread in the query.
+///   Example: `fragments = "fragments.graphql"`
+/// - `strict_enums` (optional): Whether deserializing an input enum fails on
+///   values unknown to the schema, instead of falling back to `Other`.
+///    - default: `false`
+/// - `visibility` (optional): The visibility of the generated modules and
+///   items, e.g. to keep them out of a library's public API.
+///   Example: `visibility = "pub(crate)"`
+///    - default: `"pub"`
 #[proc_macro]
 pub fn generate_types(attr: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let args = parse_macro_input!(attr as GenerateTypeArgs);
 
+    let visibility = match parse_visibility(args.visibility.as_ref()) {
+        Ok(visibility) => visibility,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
     let query_path = args
         .query_path
         .expect("No value given for query_path")
@@ -420,52 +622,371 @@ pub fn generate_types(attr: proc_macro::TokenStream) -> proc_macro::TokenStream
         .as_ref()
         .map(extract_extern_enums)
         .unwrap_or_else(default_exter_enums);
+    let derives = match args.derives.as_ref().map(extract_derives).transpose() {
+        Ok(derives) => derives.unwrap_or_default(),
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let typegen_options = TypegenOptions {
+        flatten_connections: flag(args.flatten_connections.as_ref()),
+        list_iterators: flag(args.list_iterators.as_ref()),
+        serialize_none_as_null: flag(args.serialize_none_as_null.as_ref()),
+        strict_enums: flag(args.strict_enums.as_ref()),
+    };
 
     let input_struct = generate_input_struct(
         query_path.as_str(),
         schema_path.as_str(),
         extern_enums.as_slice(),
+        derives.as_slice(),
+        args.fragments.as_ref().map(LitStr::value).as_deref(),
+        &typegen_options,
     );
     let output_query =
         "mutation Output($result: FunctionResult!) {\n    handleResult(result: $result)\n}\n";
-    let output_struct = generate_output_struct(output_query, &schema_path, extern_enums.as_slice());
+    let output_struct = generate_output_struct(
+        output_query,
+        &schema_path,
+        extern_enums.as_slice(),
+        derives.as_slice(),
+        &typegen_options,
+    );
+    let schema_docs = generate_schema_docs_module(args.schema_docs.as_ref(), &schema_path);
+    let schema_sdl = generate_schema_sdl(args.embed_schema.as_ref(), &schema_path);
+    let generated_types = generate_type_list(
+        args.generated_types.as_ref(),
+        &[&input_struct, &output_struct],
+    );
 
-    quote! {
+    let generated = quote! {
         #input_struct
         #output_struct
+        #schema_docs
+        #schema_sdl
+        #generated_types
+    };
+    match visibility {
+        Some(visibility) => set_visibility(generated, &visibility),
+        None => generated,
     }
     .into()
 }
 
+#[derive(Clone, Default)]
+struct TestFixturesArgs {
+    function: Option<Expr>,
+    path: Option<LitStr>,
+}
+
+impl TestFixturesArgs {
+    fn parse<K: syn::parse::Parse, V: syn::parse::Parse>(
+        input: &ParseStream<'_>,
+    ) -> syn::Result<V> {
+        input.parse::<K>()?;
+        input.parse::<Token![=]>()?;
+        let value: V = input.parse()?;
+        if input.lookahead1().peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(value)
+    }
+}
+
+impl Parse for TestFixturesArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut args = Self::default();
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::function) {
+                args.function = Some(Self::parse::<kw::function, Expr>(&input)?);
+            } else if lookahead.peek(kw::path) {
+                args.path = Some(Self::parse::<kw::path, LitStr>(&input)?);
+            } else {
+                return Err(lookahead.error());
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Generates one test per fixture directory.
+///
+/// Each subdirectory of `path` must contain an `input.json` file with the
+/// function input, and an `expected.json` file with the expected output. The
+/// generated test is named after the subdirectory, runs the function with
+/// `shopify_function::run_function_with_input`, and asserts that the serialized
+/// output matches `expected.json`. The fixtures are found by listing the
+/// subdirectories of `path`, rather than by matching a glob pattern.
+///
+/// Test names are the subdirectory names in `snake_case`, with characters
+/// other than ASCII letters, digits and `_` replaced by `_`, a `fixture_`
+/// prefix if they start with a digit, and a `_` suffix for Rust keywords.
+/// Directories mapping to the same test name are reported as an error.
+///
+/// Every file in the fixture directories is included in the generated code,
+/// so editing a fixture reruns its test. Cargo can't track new directories
+/// though: after adding a fixture directory, touch the file invoking the macro
+/// (or run `cargo clean`) for its test to be generated.
+///
+/// The macro takes the following parameters:
+/// - `function`: The function to test.
+/// - `path`: A path to the fixtures directory, relative to the crate root.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     shopify_function_test_fixtures!(function = function, path = "fixtures");
+/// }
+/// ```
+#[proc_macro]
+pub fn shopify_function_test_fixtures(attr: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let args = parse_macro_input!(attr as TestFixturesArgs);
+
+    let Some(function) = args.function else {
+        return syn::Error::new(Span::call_site(), "No value given for function")
+            .to_compile_error()
+            .into();
+    };
+    let Some(path) = args.path else {
+        return syn::Error::new(Span::call_site(), "No value given for path")
+            .to_compile_error()
+            .into();
+    };
+    let fixtures_dir = manifest_relative_path(&path.value());
+    let mut fixture_dirs: Vec<PathBuf> = match std::fs::read_dir(&fixtures_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.is_dir())
+            .collect(),
+        Err(error) => {
+            return syn::Error::new_spanned(path, format!("Error reading fixtures: {}", error))
+                .to_compile_error()
+                .into()
+        }
+    };
+    fixture_dirs.sort();
+
+    // Includes every fixture file so that cargo rebuilds when one changes.
+    let mut fixture_files: Vec<String> = fixture_dirs
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.is_file())
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    fixture_files.sort();
+
+    let mut dirs_by_test_name: HashMap<String, &Path> = HashMap::new();
+    let mut tests = vec![];
+    for dir in &fixture_dirs {
+        let Some(dir_name) = dir.file_name() else {
+            return syn::Error::new_spanned(
+                &path,
+                format!("Invalid fixture directory: {}", dir.display()),
+            )
+            .to_compile_error()
+            .into();
+        };
+        let name = fixture_test_name(&dir_name.to_string_lossy());
+        if let Some(other_dir) = dirs_by_test_name.insert(name.clone(), dir) {
+            return syn::Error::new_spanned(
+                &path,
+                format!(
+                    "Fixture directories {} and {} both map to the test name `{}`",
+                    other_dir.display(),
+                    dir.display(),
+                    name
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+        let input_path = dir.join("input.json");
+        let expected_path = dir.join("expected.json");
+        if let Some(missing) = [&input_path, &expected_path]
+            .into_iter()
+            .find(|path| !path.is_file())
+        {
+            return syn::Error::new_spanned(
+                &path,
+                format!("Missing fixture file: {}", missing.display()),
+            )
+            .to_compile_error()
+            .into();
+        }
+
+        let test_name = Ident::new(&name, Span::call_site());
+        let input_path = input_path.to_string_lossy().to_string();
+        let expected_path = expected_path.to_string_lossy().to_string();
+        tests.push(quote! {
+            #[test]
+            fn #test_name() -> ::shopify_function::Result<()> {
+                let result = ::shopify_function::run_function_with_input(
+                    #function,
+                    include_str!(#input_path),
+                )?;
+                let expected: serde_json::Value = serde_json::from_str(include_str!(#expected_path))?;
+                assert_eq!(serde_json::to_value(&result)?, expected);
+                Ok(())
+            }
+        });
+    }
+
+    quote! {
+        #(const _: &[u8] = include_bytes!(#fixture_files);)*
+
+        #(#tests)*
+    }
+    .into()
+}
+
+/// Turns a fixture directory name into a valid test function name.
+fn fixture_test_name(dir_name: &str) -> String {
+    let mut name: String = dir_name
+        .to_case(Case::Snake)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name = format!("fixture_{}", name);
+    }
+    if RUST_KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+    name
+}
+
+// Keywords escaped with a trailing `_` by graphql_client_codegen when used as
+// identifiers, e.g. enum variant names.
+pub(crate) const RUST_KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "union", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
 const DEFAULT_EXTERN_ENUMS: &[&str] = &["LanguageCode", "CountryCode", "CurrencyCode"];
+const DEFAULT_RESPONSE_DERIVES: &[&str] =
+    &["Clone", "Debug", "PartialEq", "Deserialize", "Serialize"];
+const DEFAULT_VARIABLES_DERIVES: &[&str] = &["Clone", "Debug", "PartialEq", "Deserialize"];
+
+/// The opt-in additions to the generated input and output types.
+struct TypegenOptions {
+    flatten_connections: bool,
+    list_iterators: bool,
+    serialize_none_as_null: bool,
+    strict_enums: bool,
+}
+
+fn flag(value: Option<&LitBool>) -> bool {
+    value.is_some_and(LitBool::value)
+}
 
 fn generate_input_struct(
     query_path: &str,
     schema_path: &str,
     extern_enums: &[String],
+    derives: &[String],
+    fragments_path: Option<&str>,
+    typegen_options: &TypegenOptions,
 ) -> TokenStream {
+    let query_path = manifest_relative_path(query_path);
+    let mut query = std::fs::read_to_string(&query_path).expect("Error reading the query");
+    // Appends the shared fragments to the query, and includes them in the
+    // generated code so it is rebuilt when they change.
+    let fragments_include = fragments_path.map(|fragments_path| {
+        let fragments_path = manifest_relative_path(fragments_path);
+        let fragments =
+            std::fs::read_to_string(&fragments_path).expect("Error reading the fragments");
+        query.push('\n');
+        query.push_str(&fragments);
+        let fragments_path = fragments_path.to_string_lossy().to_string();
+        quote! {
+            const _: &str = include_str!(#fragments_path);
+        }
+    });
+    let mut options = graphql_codegen_options("Input".to_string(), extern_enums, derives);
+    // Makes the generated code include the query, so it is rebuilt when the query changes.
+    options.set_query_file(query_path);
+    let schema_path = schema_relative_path(schema_path);
+    // Includes the schema and reads `SHOPIFY_SCHEMA_DIR` in the generated code,
+    // so it is rebuilt when either changes.
+    let schema_include = {
+        let schema_path = schema_path.to_string_lossy().to_string();
+        quote! {
+            const _: Option<&str> = option_env!("SHOPIFY_SCHEMA_DIR");
+            const _: &str = include_str!(#schema_path);
+        }
+    };
+    let token_stream = generate_module_token_stream_from_string(&query, &schema_path, options)
+        .expect("Error generating Input struct");
+    let mut generated: syn::File =
+        syn::parse2(token_stream).expect("Error parsing generated Input struct");
+    conditional::make_conditional_fields_optional(&mut generated, &query);
+    enums::add_enum_impls(&mut generated);
+    field_names::add_field_names(&mut generated);
+    cost::add_cost_impls(&mut generated);
+    unions::add_union_impls(&mut generated);
+    if typegen_options.strict_enums {
+        enums::make_enums_strict(&mut generated);
+    }
+    if typegen_options.flatten_connections {
+        connections::add_connection_accessors(&mut generated);
+    }
+    if typegen_options.list_iterators {
+        iterators::add_iterator_impls(&mut generated);
+    }
+
     quote! {
-        #[derive(graphql_client::GraphQLQuery, Clone, Debug, serde::Deserialize, PartialEq)]
-        #[graphql(
-            query_path = #query_path,
-            schema_path = #schema_path,
-            response_derives = "Clone,Debug,PartialEq,Deserialize,Serialize",
-            variables_derives = "Clone,Debug,PartialEq,Deserialize",
-            extern_enums(#(#extern_enums),*),
-            skip_serializing_none
-        )]
+        #[derive(Clone, Debug, serde::Deserialize, PartialEq)]
         pub struct Input;
+        #generated
+        #fragments_include
+        #schema_include
+
+        /// Builds the input from a JSON value, to streamline setting up tests.
+        #[cfg(test)]
+        impl ::std::convert::TryFrom<serde_json::Value> for input::ResponseData {
+            type Error = serde_json::Error;
+
+            fn try_from(value: serde_json::Value) -> ::std::result::Result<Self, Self::Error> {
+                serde_json::from_value(value)
+            }
+        }
+
+        /// Converts the input back to JSON, e.g. to log it in a failing test.
+        #[cfg(test)]
+        impl ::std::convert::From<input::ResponseData> for serde_json::Value {
+            fn from(value: input::ResponseData) -> Self {
+                serde_json::to_value(value).expect("Error serializing input to JSON")
+            }
+        }
     }
 }
 
 fn graphql_codegen_options(
     operation_name: String,
     extern_enums: &[String],
+    derives: &[String],
 ) -> GraphQLClientCodegenOptions {
     let mut options = GraphQLClientCodegenOptions::new(CodegenMode::Derive);
     options.set_operation_name(operation_name);
-    options.set_response_derives("Clone,Debug,PartialEq,Deserialize,Serialize".to_string());
-    options.set_variables_derives("Clone,Debug,PartialEq,Deserialize".to_string());
+    options.set_response_derives(join_derives(DEFAULT_RESPONSE_DERIVES, &[], derives));
+    // graphql_client always derives `Serialize` on the variables.
+    options.set_variables_derives(join_derives(
+        DEFAULT_VARIABLES_DERIVES,
+        &["Serialize"],
+        derives,
+    ));
     options.set_skip_serializing_none(true);
     options.set_module_visibility(
         syn::VisPublic {
@@ -482,20 +1003,255 @@ fn generate_output_struct(
     query: &str,
     schema_path: &str,
     extern_enums: &[String],
+    derives: &[String],
+    typegen_options: &TypegenOptions,
 ) -> proc_macro2::TokenStream {
-    let options = graphql_codegen_options("Output".to_string(), extern_enums);
-    let cargo_manifest_dir =
-        std::env::var("CARGO_MANIFEST_DIR").expect("Error reading CARGO_MANIFEST_DIR from env");
-    let schema_path = Path::new(&cargo_manifest_dir).join(schema_path);
+    let mut options = graphql_codegen_options("Output".to_string(), extern_enums, derives);
+    options.set_skip_serializing_none(!typegen_options.serialize_none_as_null);
+    let schema_path = schema_relative_path(schema_path);
     let token_stream = generate_module_token_stream_from_string(query, &schema_path, options)
         .expect("Error generating Output struct");
+    let mut generated: syn::File =
+        syn::parse2(token_stream).expect("Error parsing generated Output struct");
+    enums::add_enum_impls(&mut generated);
+    field_names::add_field_names(&mut generated);
+    one_of::add_one_of_impls(&mut generated);
+    if typegen_options.list_iterators {
+        iterators::add_iterator_impls(&mut generated);
+    }
+
+    // Building a result without returning it is almost always a mistake.
+    for item in generated_module_items(&mut generated) {
+        match item {
+            syn::Item::Struct(item) => item.attrs.push(syn::parse_quote!(#[must_use])),
+            syn::Item::Enum(item) => item.attrs.push(syn::parse_quote!(#[must_use])),
+            _ => {}
+        }
+    }
 
     quote! {
-        #token_stream
+        #generated
         pub struct Output;
     }
 }
 
+/// Returns the items declared inside the modules generated by graphql_client.
+pub(crate) fn generated_module_items(
+    generated: &mut syn::File,
+) -> impl Iterator<Item = &mut syn::Item> {
+    generated
+        .items
+        .iter_mut()
+        .filter_map(|item| match item {
+            syn::Item::Mod(module) => module.content.as_mut(),
+            _ => None,
+        })
+        .flat_map(|(_, items)| items.iter_mut())
+}
+
+/// Returns the name of the struct field's type, if it's a plain (non-optional) type name.
+pub(crate) fn field_type_name(item: &syn::ItemStruct, name: &str) -> Option<String> {
+    field_type(item, name).and_then(type_name)
+}
+
+/// Returns the type of the struct field with the given name.
+pub(crate) fn field_type<'a>(item: &'a syn::ItemStruct, name: &str) -> Option<&'a syn::Type> {
+    let syn::Fields::Named(fields) = &item.fields else {
+        return None;
+    };
+    fields
+        .named
+        .iter()
+        .find(|field| field.ident.as_ref().is_some_and(|ident| ident == name))
+        .map(|field| &field.ty)
+}
+
+/// Returns the name of the type, if it's a plain type name.
+pub(crate) fn type_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            path.path.get_ident().map(|ident| ident.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Returns `T` for a `Vec<T>`.
+pub(crate) fn vec_item_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        syn::GenericArgument::Type(item) => Some(item),
+        _ => None,
+    }
+}
+
+/// Returns the name of the field in JSON, i.e. its serde `rename` or its identifier.
+pub(crate) fn field_wire_name(field: &syn::Field) -> String {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .find_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested.iter().find_map(|meta| match meta {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                    if name_value.path.is_ident("rename") =>
+                {
+                    match &name_value.lit {
+                        syn::Lit::Str(rename) => Some(rename.value()),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }),
+            _ => None,
+        })
+        .unwrap_or_else(|| {
+            field
+                .ident
+                .as_ref()
+                .map(|ident| ident.to_string())
+                .unwrap_or_default()
+        })
+}
+
+fn generate_schema_docs_module(schema_docs: Option<&LitBool>, schema_path: &str) -> TokenStream {
+    if !schema_docs.is_some_and(LitBool::value) {
+        return quote! {};
+    }
+    let schema = std::fs::read_to_string(schema_relative_path(schema_path))
+        .expect("Error reading the schema for schema_docs");
+    schema_docs::generate_schema_docs(&schema)
+}
+
+fn generate_schema_sdl(embed_schema: Option<&LitBool>, schema_path: &str) -> TokenStream {
+    if !embed_schema.is_some_and(LitBool::value) {
+        return quote! {};
+    }
+    let schema_path = schema_relative_path(schema_path)
+        .to_string_lossy()
+        .to_string();
+    quote! {
+        /// The GraphQL schema SDL the types were generated from.
+        pub const SCHEMA_SDL: &str = include_str!(#schema_path);
+    }
+}
+
+fn generate_type_list(
+    generated_types: Option<&LitBool>,
+    generated: &[&TokenStream],
+) -> TokenStream {
+    if !generated_types.is_some_and(LitBool::value) {
+        return quote! {};
+    }
+    let mut type_paths = vec![];
+    for tokens in generated {
+        let file: syn::File =
+            syn::parse2((*tokens).clone()).expect("Error parsing generated types");
+        for item in file.items {
+            let syn::Item::Mod(module) = item else {
+                continue;
+            };
+            let Some((_, items)) = module.content else {
+                continue;
+            };
+            type_paths.extend(items.iter().filter_map(|item| match item {
+                syn::Item::Struct(syn::ItemStruct { vis, ident, .. })
+                | syn::Item::Enum(syn::ItemEnum { vis, ident, .. })
+                    if matches!(vis, syn::Visibility::Public(_)) =>
+                {
+                    Some(format!("{}::{}", module.ident, ident))
+                }
+                _ => None,
+            }));
+        }
+    }
+    quote! {
+        /// The paths of the types generated from the query and schema.
+        pub const GENERATED_TYPES: &[&str] = &[#(#type_paths),*];
+    }
+}
+
+fn parse_visibility(visibility: Option<&LitStr>) -> syn::Result<Option<syn::Visibility>> {
+    visibility.map(LitStr::parse::<syn::Visibility>).transpose()
+}
+
+/// Replaces the `pub` visibility of the generated items, including the items
+/// nested in the generated modules.
+fn set_visibility(generated: TokenStream, visibility: &syn::Visibility) -> TokenStream {
+    let mut generated: syn::File = syn::parse2(generated).expect("Error parsing generated types");
+    set_items_visibility(&mut generated.items, visibility, 0);
+    generated.into_token_stream()
+}
+
+fn set_items_visibility(items: &mut [syn::Item], visibility: &syn::Visibility, depth: usize) {
+    for item in items.iter_mut() {
+        let vis = match item {
+            syn::Item::Mod(item) => {
+                if let Some((_, items)) = item.content.as_mut() {
+                    set_items_visibility(items, visibility, depth + 1);
+                }
+                &mut item.vis
+            }
+            syn::Item::Struct(item) => &mut item.vis,
+            syn::Item::Enum(item) => &mut item.vis,
+            syn::Item::Union(item) => &mut item.vis,
+            syn::Item::Type(item) => &mut item.vis,
+            syn::Item::Const(item) => &mut item.vis,
+            syn::Item::Static(item) => &mut item.vis,
+            syn::Item::Fn(item) => &mut item.vis,
+            syn::Item::Trait(item) => &mut item.vis,
+            syn::Item::Use(item) => &mut item.vis,
+            _ => continue,
+        };
+        if matches!(vis, syn::Visibility::Public(_)) {
+            *vis = nested_visibility(visibility, depth);
+        }
+    }
+}
+
+/// Rewrites a visibility relative to the macro call site, such as
+/// `pub(super)`, for an item `depth` modules below it.
+fn nested_visibility(visibility: &syn::Visibility, depth: usize) -> syn::Visibility {
+    let syn::Visibility::Restricted(restricted) = visibility else {
+        return visibility.clone();
+    };
+    let mut segments = restricted.path.segments.iter().peekable();
+    match segments.peek() {
+        Some(segment) if depth > 0 && segment.ident == "self" => {
+            segments.next();
+        }
+        Some(segment) if depth > 0 && segment.ident == "super" => {}
+        _ => return visibility.clone(),
+    }
+    let segments =
+        std::iter::repeat_n(quote!(super), depth).chain(segments.map(ToTokens::to_token_stream));
+    syn::parse_quote!(pub(in #(#segments)::*))
+}
+
+/// Resolves the schema path relative to the `SHOPIFY_SCHEMA_DIR` env var when
+/// set, e.g. for a schema shared by several crates, or to `CARGO_MANIFEST_DIR`.
+fn schema_relative_path(path: &str) -> PathBuf {
+    match std::env::var("SHOPIFY_SCHEMA_DIR") {
+        Ok(schema_dir) => manifest_relative_path(&schema_dir).join(path),
+        Err(_) => manifest_relative_path(path),
+    }
+}
+
+fn manifest_relative_path(path: &str) -> PathBuf {
+    let cargo_manifest_dir =
+        std::env::var("CARGO_MANIFEST_DIR").expect("Error reading CARGO_MANIFEST_DIR from env");
+    Path::new(&cargo_manifest_dir).join(path)
+}
+
 fn extract_extern_enums(extern_enums: &ExprArray) -> Vec<String> {
     let extern_enum_error_msg = r#"The `extern_enums` attribute expects comma separated string literals\n\n= help: use `extern_enums = ["Enum1", "Enum2"]`"#;
     extern_enums
@@ -514,12 +1270,155 @@ fn extract_extern_enums(extern_enums: &ExprArray) -> Vec<String> {
         .collect()
 }
 
+fn extract_derives(derives: &ExprArray) -> syn::Result<Vec<String>> {
+    let derives_error_msg = "The `derives` attribute expects comma separated paths to derive macros\n\n= help: use `derives = [::std::cmp::PartialOrd]`";
+    derives
+        .elems
+        .iter()
+        .map(|expr| match expr {
+            Expr::Path(path) if path.qself.is_none() => {
+                Ok(path.path.to_token_stream().to_string().replace(' ', ""))
+            }
+            _ => Err(syn::Error::new_spanned(expr, derives_error_msg)),
+        })
+        .collect()
+}
+
+/// Joins the default derives with the additional `derives`, skipping those
+/// already derived, either as defaults or by graphql_client itself, since
+/// deriving a trait twice is a compile error.
+fn join_derives(defaults: &[&str], implied: &[&str], derives: &[String]) -> String {
+    let is_derived = |derive: &String| {
+        defaults
+            .iter()
+            .chain(implied)
+            .any(|default| is_same_derive(derive, default))
+    };
+    defaults
+        .iter()
+        .map(|derive| derive.to_string())
+        .chain(derives.iter().filter(|derive| !is_derived(derive)).cloned())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Whether the derive path names the default derive, either by its name, as
+/// imported in the generated modules, or by its full `std`, `core` or `serde`
+/// path.
+fn is_same_derive(derive: &str, default: &str) -> bool {
+    let full_paths: &[&str] = match default {
+        "Clone" => &["std::clone::Clone", "core::clone::Clone"],
+        "Debug" => &["std::fmt::Debug", "core::fmt::Debug"],
+        "PartialEq" => &["std::cmp::PartialEq", "core::cmp::PartialEq"],
+        "Deserialize" => &["serde::Deserialize"],
+        "Serialize" => &["serde::Serialize"],
+        _ => &[],
+    };
+    derive == default || full_paths.contains(&derive.trim_start_matches("::"))
+}
+
 fn default_exter_enums() -> Vec<String> {
     DEFAULT_EXTERN_ENUMS.iter().map(|e| e.to_string()).collect()
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_derives_skips_defaults() {
+        let derives = vec![
+            "::serde::Serialize".to_string(),
+            "std::clone::Clone".to_string(),
+            "Eq".to_string(),
+        ];
+        assert_eq!(
+            join_derives(DEFAULT_RESPONSE_DERIVES, &[], &derives),
+            "Clone,Debug,PartialEq,Deserialize,Serialize,Eq"
+        );
+        assert_eq!(
+            join_derives(DEFAULT_VARIABLES_DERIVES, &["Serialize"], &derives),
+            "Clone,Debug,PartialEq,Deserialize,Eq"
+        );
+    }
+
+    #[test]
+    fn test_join_derives_keeps_other_crates_derives() {
+        let derives = vec![
+            "my_crate::Debug".to_string(),
+            "other::Deserialize".to_string(),
+        ];
+        assert_eq!(
+            join_derives(DEFAULT_VARIABLES_DERIVES, &["Serialize"], &derives),
+            "Clone,Debug,PartialEq,Deserialize,my_crate::Debug,other::Deserialize"
+        );
+    }
+
+    #[test]
+    fn test_fixture_test_name() {
+        assert_eq!(
+            fixture_test_name("discount-with-config"),
+            "discount_with_config"
+        );
+        assert_eq!(fixture_test_name("v1.2 cart"), "v_1_2_cart");
+        assert_eq!(fixture_test_name("2024_sale"), "fixture_2024_sale");
+        assert_eq!(fixture_test_name("type"), "type_");
+        assert_eq!(fixture_test_name("été"), "_t_");
+    }
+
+    #[test]
+    fn test_extract_derives_error() {
+        let derives: ExprArray = syn::parse_quote!([Eq, "Hash"]);
+        let error = extract_derives(&derives).unwrap_err();
+        assert!(error.to_string().contains("expects comma separated paths"));
+    }
+
+    #[test]
+    fn test_set_visibility_of_nested_items() {
+        let generated = quote! {
+            pub mod input {
+                pub enum Kind { A }
+                pub type Id = String;
+                pub mod cart {
+                    pub struct Line;
+                }
+            }
+        };
+        let expected = quote! {
+            pub(crate) mod input {
+                pub(crate) enum Kind { A }
+                pub(crate) type Id = String;
+                pub(crate) mod cart {
+                    pub(crate) struct Line;
+                }
+            }
+        };
+        let visibility = syn::parse_quote!(pub(crate));
+        assert_eq!(
+            expected.to_string(),
+            set_visibility(generated, &visibility).to_string()
+        );
+    }
+
+    #[test]
+    fn test_set_relative_visibility_of_nested_items() {
+        let generated = quote! {
+            pub mod input {
+                pub struct Cart;
+            }
+        };
+        let expected = quote! {
+            pub(super) mod input {
+                pub(in super::super) struct Cart;
+            }
+        };
+        let visibility = syn::parse_quote!(pub(super));
+        assert_eq!(
+            expected.to_string(),
+            set_visibility(generated, &visibility).to_string()
+        );
+    }
+}
 
 mod kw {
     syn::custom_keyword!(target);
@@ -529,4 +1428,16 @@ mod kw {
     syn::custom_keyword!(input_stream);
     syn::custom_keyword!(output_stream);
     syn::custom_keyword!(extern_enums);
+    syn::custom_keyword!(derives);
+    syn::custom_keyword!(schema_docs);
+    syn::custom_keyword!(embed_schema);
+    syn::custom_keyword!(flatten_connections);
+    syn::custom_keyword!(generated_types);
+    syn::custom_keyword!(list_iterators);
+    syn::custom_keyword!(serialize_none_as_null);
+    syn::custom_keyword!(fragments);
+    syn::custom_keyword!(strict_enums);
+    syn::custom_keyword!(visibility);
+    syn::custom_keyword!(function);
+    syn::custom_keyword!(path);
 }
@@ -0,0 +1,166 @@
+use crate::{type_name, RUST_KEYWORDS};
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use std::collections::HashMap;
+use syn::{Fields, Item, ItemEnum, ItemStruct};
+
+/// Adds a constructor per variant to the enums generated for `@oneOf` input
+/// objects, taking the fields of the variant's input object as arguments, e.g.
+/// `Value::percentage(value)` for `Value::Percentage(Percentage { value })`.
+/// The wrapped input objects also implement `TryFrom` the enum, owned and
+/// borrowed.
+pub(crate) fn add_one_of_impls(generated: &mut syn::File) {
+    for module in generated.items.iter_mut() {
+        let Item::Mod(module) = module else {
+            continue;
+        };
+        let Some((_, items)) = module.content.as_mut() else {
+            continue;
+        };
+        let structs_by_name: HashMap<String, &ItemStruct> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Struct(item) => Some((item.ident.to_string(), item)),
+                _ => None,
+            })
+            .collect();
+
+        let impls: Vec<Item> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Enum(item) if is_one_of_enum(item) => {
+                    let mut impls = try_from_impls(item, &structs_by_name);
+                    impls.push(constructors(item, &structs_by_name));
+                    Some(impls)
+                }
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        items.extend(impls);
+    }
+}
+
+/// `@oneOf` input objects are generated as enums whose variants all wrap a
+/// single value, while schema enums have unit variants.
+fn is_one_of_enum(item: &ItemEnum) -> bool {
+    item.variants.iter().all(
+        |variant| matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1),
+    )
+}
+
+fn constructors(item: &ItemEnum, structs_by_name: &HashMap<String, &ItemStruct>) -> Item {
+    let name = &item.ident;
+    let constructors = item.variants.iter().map(|variant| {
+        let variant_name = &variant.ident;
+        let mut constructor = variant_name
+            .to_string()
+            .trim_end_matches('_')
+            .to_case(Case::Snake);
+        if RUST_KEYWORDS.contains(&constructor.as_str()) {
+            constructor.push('_');
+        }
+        let constructor = Ident::new(&constructor, Span::call_site());
+        let Fields::Unnamed(fields) = &variant.fields else {
+            unreachable!("oneOf variants wrap a single value");
+        };
+        let ty = &fields.unnamed[0].ty;
+        let doc = format!(" Builds a [`{name}::{variant_name}`].");
+
+        let fields = type_name(ty)
+            .and_then(|type_name| structs_by_name.get(&type_name))
+            .and_then(|item| match &item.fields {
+                Fields::Named(fields) => Some(&fields.named),
+                _ => None,
+            });
+        match fields {
+            Some(fields) => {
+                let idents = fields.iter().map(|field| &field.ident);
+                let args = fields.iter().map(|field| {
+                    let ident = &field.ident;
+                    let ty = &field.ty;
+                    quote!(#ident: #ty)
+                });
+                quote! {
+                    #[doc = #doc]
+                    #[allow(clippy::too_many_arguments)]
+                    pub fn #constructor(#(#args),*) -> Self {
+                        Self::#variant_name(#ty { #(#idents),* })
+                    }
+                }
+            }
+            None => quote! {
+                #[doc = #doc]
+                pub fn #constructor(value: #ty) -> Self {
+                    Self::#variant_name(value)
+                }
+            },
+        }
+    });
+
+    syn::parse_quote! {
+        impl #name {
+            #(#constructors)*
+        }
+    }
+}
+
+/// Implements `TryFrom` the enum for the input objects wrapped by a single
+/// variant, failing with the enum itself for the other variants. Scalars are
+/// skipped, as several of them can alias the same Rust type, e.g. `String`.
+fn try_from_impls(item: &ItemEnum, structs_by_name: &HashMap<String, &ItemStruct>) -> Vec<Item> {
+    let name = &item.ident;
+    let variant_types: Vec<(&Ident, &syn::Type)> = item
+        .variants
+        .iter()
+        .filter_map(|variant| match &variant.fields {
+            Fields::Unnamed(fields) => Some((&variant.ident, &fields.unnamed[0].ty)),
+            _ => None,
+        })
+        .collect();
+
+    variant_types
+        .iter()
+        .filter(|(_, ty)| {
+            let Some(name) = type_name(ty).filter(|name| structs_by_name.contains_key(name)) else {
+                return false;
+            };
+            variant_types
+                .iter()
+                .filter(|(_, other)| type_name(other).as_ref() == Some(&name))
+                .count()
+                == 1
+        })
+        .flat_map(|(variant_name, ty)| -> [Item; 2] {
+            [
+                syn::parse_quote! {
+                    impl ::std::convert::TryFrom<#name> for #ty {
+                        type Error = #name;
+
+                        fn try_from(value: #name) -> ::std::result::Result<Self, Self::Error> {
+                            match value {
+                                #name::#variant_name(inner) => Ok(inner),
+                                #[allow(unreachable_patterns)]
+                                other => Err(other),
+                            }
+                        }
+                    }
+                },
+                syn::parse_quote! {
+                    impl<'a> ::std::convert::TryFrom<&'a #name> for &'a #ty {
+                        type Error = &'a #name;
+
+                        fn try_from(value: &'a #name) -> ::std::result::Result<Self, Self::Error> {
+                            match value {
+                                #name::#variant_name(inner) => Ok(inner),
+                                #[allow(unreachable_patterns)]
+                                other => Err(other),
+                            }
+                        }
+                    }
+                },
+            ]
+        })
+        .collect()
+}
@@ -0,0 +1,89 @@
+use graphql_parser::schema::{Definition, TypeDefinition};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Generates a `schema_docs` module exposing the descriptions found in the
+/// GraphQL schema, keyed by type name and by type and field name.
+pub(crate) fn generate_schema_docs(schema: &str) -> TokenStream {
+    let document = graphql_parser::parse_schema::<String>(schema)
+        .expect("Error parsing the schema for schema_docs");
+
+    let mut types = vec![];
+    let mut fields = vec![];
+    for definition in document.definitions {
+        let Definition::TypeDefinition(definition) = definition else {
+            continue;
+        };
+        let (type_name, description, type_fields) = match definition {
+            TypeDefinition::Scalar(t) => (t.name, t.description, vec![]),
+            TypeDefinition::Union(t) => (t.name, t.description, vec![]),
+            TypeDefinition::Object(t) => (
+                t.name,
+                t.description,
+                t.fields
+                    .into_iter()
+                    .map(|f| (f.name, f.description))
+                    .collect(),
+            ),
+            TypeDefinition::Interface(t) => (
+                t.name,
+                t.description,
+                t.fields
+                    .into_iter()
+                    .map(|f| (f.name, f.description))
+                    .collect(),
+            ),
+            TypeDefinition::InputObject(t) => (
+                t.name,
+                t.description,
+                t.fields
+                    .into_iter()
+                    .map(|f| (f.name, f.description))
+                    .collect(),
+            ),
+            TypeDefinition::Enum(t) => (
+                t.name,
+                t.description,
+                t.values
+                    .into_iter()
+                    .map(|v| (v.name, v.description))
+                    .collect(),
+            ),
+        };
+        if let Some(description) = description.as_deref().map(str::trim) {
+            types.push(quote! { (#type_name, #description) });
+        }
+        for (field_name, description) in type_fields {
+            if let Some(description) = description.as_deref().map(str::trim) {
+                fields.push(quote! { (#type_name, #field_name, #description) });
+            }
+        }
+    }
+
+    quote! {
+        pub mod schema_docs {
+            /// Descriptions of the schema types, as `(type, description)`.
+            pub const TYPES: &[(&str, &str)] = &[#(#types),*];
+
+            /// Descriptions of the schema fields, input fields and enum values,
+            /// as `(type, field, description)`.
+            pub const FIELDS: &[(&str, &str, &str)] = &[#(#fields),*];
+
+            /// Returns the description of the given schema type, if any.
+            pub fn type_description(type_name: &str) -> Option<&'static str> {
+                TYPES
+                    .iter()
+                    .find(|(name, _)| *name == type_name)
+                    .map(|(_, description)| *description)
+            }
+
+            /// Returns the description of the given field of a schema type, if any.
+            pub fn field_description(type_name: &str, field_name: &str) -> Option<&'static str> {
+                FIELDS
+                    .iter()
+                    .find(|(ty, field, _)| *ty == type_name && *field == field_name)
+                    .map(|(_, _, description)| *description)
+            }
+        }
+    }
+}
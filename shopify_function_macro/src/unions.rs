@@ -0,0 +1,157 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Fields, Item, ItemEnum, Meta, NestedMeta};
+
+/// Adds an `Other(String)` variant to the union and interface enums generated
+/// by graphql_client, i.e. enums tagged with `__typename`, keeping the
+/// `__typename` of the types without a variant, and a `kind()` method
+/// returning the `__typename` of the variant.
+///
+/// Serde can't deserialize an internally tagged enum into a variant holding
+/// the tag, so the derived `Deserialize` and `Serialize` impls are replaced.
+pub(crate) fn add_union_impls(generated: &mut syn::File) {
+    for module in generated.items.iter_mut() {
+        let Item::Mod(module) = module else {
+            continue;
+        };
+        let Some((_, items)) = module.content.as_mut() else {
+            continue;
+        };
+        let mut impls: Vec<Item> = vec![];
+        for item in items.iter_mut() {
+            let Item::Enum(item) = item else {
+                continue;
+            };
+            if !is_union_enum(item) {
+                continue;
+            }
+            remove_serde_derives(item);
+            item.variants.push(syn::parse_quote! {
+                /// A type without a variant, holding its `__typename`.
+                Other(String)
+            });
+            impls.extend(
+                syn::parse2::<syn::File>(union_impls(item))
+                    .expect("Error parsing generated union impls")
+                    .items,
+            );
+        }
+        items.extend(impls);
+    }
+}
+
+fn is_union_enum(item: &ItemEnum) -> bool {
+    item.attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("serde") && attr.tokens.to_string().contains("__typename"))
+}
+
+/// Removes the `#[serde(tag = "__typename")]` attribute, and `Deserialize`
+/// and `Serialize` from the derives.
+fn remove_serde_derives(item: &mut ItemEnum) {
+    item.attrs.retain(|attr| !attr.path.is_ident("serde"));
+    for attr in item.attrs.iter_mut() {
+        if !attr.path.is_ident("derive") {
+            continue;
+        }
+        let Ok(Meta::List(list)) = attr.parse_meta() else {
+            continue;
+        };
+        let derives = list.nested.iter().filter(|derive| {
+            !matches!(derive, NestedMeta::Meta(Meta::Path(path)) if path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Deserialize" || segment.ident == "Serialize"))
+        });
+        *attr = syn::parse_quote!(#[derive(#(#derives),*)]);
+    }
+}
+
+fn union_impls(item: &ItemEnum) -> TokenStream {
+    let name = &item.ident;
+    let variants: Vec<(&syn::Ident, String, bool)> = item
+        .variants
+        .iter()
+        .filter(|variant| variant.ident != "Other")
+        .map(|variant| {
+            let is_unit = matches!(variant.fields, Fields::Unit);
+            (&variant.ident, variant.ident.to_string(), is_unit)
+        })
+        .collect();
+
+    let kind_arms = variants.iter().map(|(ident, typename, is_unit)| {
+        if *is_unit {
+            quote!(Self::#ident => #typename)
+        } else {
+            quote!(Self::#ident(..) => #typename)
+        }
+    });
+    let deserialize_arms = variants.iter().map(|(ident, typename, is_unit)| {
+        if *is_unit {
+            quote!(#typename => Ok(Self::#ident))
+        } else {
+            quote! {
+                #typename => ::serde::Deserialize::deserialize(value)
+                    .map(Self::#ident)
+                    .map_err(::serde::de::Error::custom)
+            }
+        }
+    });
+    let serialize_arms = variants.iter().map(|(ident, typename, is_unit)| {
+        if *is_unit {
+            quote!(Self::#ident => (#typename, ::serde_json::Map::new()))
+        } else {
+            quote! {
+                Self::#ident(inner) => match ::serde_json::to_value(inner)
+                    .map_err(::serde::ser::Error::custom)?
+                {
+                    ::serde_json::Value::Object(fields) => (#typename, fields),
+                    _ => return Err(::serde::ser::Error::custom("Expected an object")),
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl #name {
+            /// The `__typename` of the variant.
+            pub fn kind(&self) -> &str {
+                match self {
+                    #(#kind_arms,)*
+                    Self::Other(typename) => typename.as_str(),
+                }
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for #name {
+            fn deserialize<D: ::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::std::result::Result<Self, D::Error> {
+                let value: ::serde_json::Value = ::serde::Deserialize::deserialize(deserializer)?;
+                let typename = value
+                    .get("__typename")
+                    .and_then(::serde_json::Value::as_str)
+                    .ok_or_else(|| ::serde::de::Error::missing_field("__typename"))?
+                    .to_string();
+                match typename.as_str() {
+                    #(#deserialize_arms,)*
+                    _ => Ok(Self::Other(typename)),
+                }
+            }
+        }
+
+        impl ::serde::Serialize for #name {
+            fn serialize<S: ::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::std::result::Result<S::Ok, S::Error> {
+                let (typename, mut fields) = match self {
+                    #(#serialize_arms,)*
+                    Self::Other(typename) => (typename.as_str(), ::serde_json::Map::new()),
+                };
+                fields.insert("__typename".to_string(), typename.into());
+                ::serde::Serialize::serialize(&fields, serializer)
+            }
+        }
+    }
+}